@@ -0,0 +1,8 @@
+-- The height of the last block which is fully stored in all the tables.
+-- The indexer resumes from the next block if --start-block-height is not provided
+CREATE TABLE _checkpoints
+(
+    indexer_id                  text           NOT NULL,
+    last_processed_block_height numeric(20, 0) NOT NULL,
+    PRIMARY KEY (indexer_id)
+);
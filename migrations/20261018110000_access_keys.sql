@@ -0,0 +1,27 @@
+-- One row per lifetime of the key: the key which is deleted and added again gets the new row.
+-- index_in_block keeps both lifetimes if the key is deleted and added again in the same block
+-- permission_kind options:
+--      {
+--         'FULL_ACCESS',
+--         'FUNCTION_CALL'
+--      }
+CREATE TABLE access_keys
+(
+    public_key                 text           NOT NULL,
+    account_id                 text           NOT NULL,
+    created_by_receipt_id      text           NOT NULL,
+    deleted_by_receipt_id      text,
+    created_by_block_timestamp numeric(20, 0) NOT NULL,
+    index_in_block             integer        NOT NULL,
+    deleted_by_block_timestamp numeric(20, 0),
+    permission_kind            text           NOT NULL,
+    permission                 jsonb          NOT NULL,
+    PRIMARY KEY (public_key, account_id, created_by_block_timestamp, index_in_block)
+);
+ALTER TABLE access_keys
+    ADD CONSTRAINT access_keys_created_by_receipt_id_fk FOREIGN KEY (created_by_receipt_id) REFERENCES action_receipts (receipt_id);
+ALTER TABLE access_keys
+    ADD CONSTRAINT access_keys_deleted_by_receipt_id_fk FOREIGN KEY (deleted_by_receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY access_keys_account_id_idx ON access_keys (account_id);
+CREATE INDEX CONCURRENTLY access_keys_created_by_block_timestamp_idx ON access_keys (created_by_block_timestamp);
+CREATE INDEX CONCURRENTLY access_keys_deleted_by_block_timestamp_idx ON access_keys (deleted_by_block_timestamp);
@@ -0,0 +1,20 @@
+-- One row per lifetime of the account: the account which is deleted and created again gets the new row.
+-- index_in_block keeps both lifetimes if the account is deleted and created again in the same block.
+-- Implicit accounts are created by the transfer to them, created_by_receipt_id is the receipt of that transfer
+CREATE TABLE accounts
+(
+    account_id                 text           NOT NULL,
+    created_by_receipt_id      text           NOT NULL,
+    deleted_by_receipt_id      text,
+    created_by_block_timestamp numeric(20, 0) NOT NULL,
+    index_in_block             integer        NOT NULL,
+    deleted_by_block_timestamp numeric(20, 0),
+    beneficiary_account_id     text,
+    PRIMARY KEY (account_id, created_by_block_timestamp, index_in_block)
+);
+ALTER TABLE accounts
+    ADD CONSTRAINT accounts_created_by_receipt_id_fk FOREIGN KEY (created_by_receipt_id) REFERENCES action_receipts (receipt_id);
+ALTER TABLE accounts
+    ADD CONSTRAINT accounts_deleted_by_receipt_id_fk FOREIGN KEY (deleted_by_receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY accounts_created_by_block_timestamp_idx ON accounts (created_by_block_timestamp);
+CREATE INDEX CONCURRENTLY accounts_deleted_by_block_timestamp_idx ON accounts (deleted_by_block_timestamp);
@@ -0,0 +1,21 @@
+-- standard, version, event and data are filled for the logs in the events format (NEP-297):
+-- EVENT_JSON:{"standard": "nep141", "version": "1.0.0", "event": "ft_transfer", "data": [...]}
+CREATE TABLE execution_outcome_logs
+(
+    receipt_id          text           NOT NULL,
+    log_index           integer        NOT NULL,
+    block_hash          text           NOT NULL,
+    block_timestamp     numeric(20, 0) NOT NULL,
+    executor_account_id text           NOT NULL,
+    log                 text           NOT NULL,
+    standard            text,
+    version             text,
+    event               text,
+    data                jsonb,
+    PRIMARY KEY (receipt_id, log_index)
+);
+ALTER TABLE execution_outcome_logs
+    ADD CONSTRAINT execution_outcome_logs_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES execution_outcomes (receipt_id);
+CREATE INDEX CONCURRENTLY execution_outcome_logs_block_timestamp_idx ON execution_outcome_logs (block_timestamp);
+CREATE INDEX CONCURRENTLY execution_outcome_logs_executor_idx ON execution_outcome_logs (executor_account_id);
+CREATE INDEX CONCURRENTLY execution_outcome_logs_standard_event_idx ON execution_outcome_logs (standard, event) WHERE standard IS NOT NULL;
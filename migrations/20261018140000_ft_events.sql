@@ -0,0 +1,26 @@
+-- NEP-141 events, one row per affected account.
+-- ft_transfer gives two rows: the negative delta for the sender and the positive one for the receiver
+-- cause options:
+--      {
+--         'MINT',
+--         'TRANSFER',
+--         'BURN'
+--      }
+CREATE TABLE ft_events
+(
+    receipt_id          text           NOT NULL,
+    event_index         integer        NOT NULL,
+    block_timestamp     numeric(20, 0) NOT NULL,
+    contract_account_id text           NOT NULL,
+    affected_account_id text           NOT NULL,
+    involved_account_id text,
+    delta_amount        numeric(45, 0) NOT NULL,
+    cause               text           NOT NULL,
+    memo                text,
+    PRIMARY KEY (receipt_id, event_index)
+);
+ALTER TABLE ft_events
+    ADD CONSTRAINT ft_events_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY ft_events_block_timestamp_idx ON ft_events (block_timestamp);
+CREATE INDEX CONCURRENTLY ft_events_contract_idx ON ft_events (contract_account_id);
+CREATE INDEX CONCURRENTLY ft_events_affected_account_idx ON ft_events (affected_account_id);
@@ -0,0 +1,27 @@
+-- NEP-171 events, one row per token mentioned in the event
+-- event_kind options:
+--      {
+--         'MINT',
+--         'TRANSFER',
+--         'BURN'
+--      }
+CREATE TABLE nft_events
+(
+    receipt_id          text           NOT NULL,
+    event_index         integer        NOT NULL,
+    block_timestamp     numeric(20, 0) NOT NULL,
+    contract_account_id text           NOT NULL,
+    token_id            text           NOT NULL,
+    event_kind          text           NOT NULL,
+    old_owner_id        text,
+    new_owner_id        text,
+    authorized_id       text,
+    memo                text,
+    PRIMARY KEY (receipt_id, event_index)
+);
+ALTER TABLE nft_events
+    ADD CONSTRAINT nft_events_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY nft_events_block_timestamp_idx ON nft_events (block_timestamp);
+CREATE INDEX CONCURRENTLY nft_events_contract_token_idx ON nft_events (contract_account_id, token_id);
+CREATE INDEX CONCURRENTLY nft_events_old_owner_idx ON nft_events (old_owner_id);
+CREATE INDEX CONCURRENTLY nft_events_new_owner_idx ON nft_events (new_owner_id);
@@ -0,0 +1,6 @@
+-- success_value_json is filled only if the success value is a valid JSON.
+-- failure keeps TxExecutionError for the outcomes with FAILURE status
+ALTER TABLE execution_outcomes
+    ADD COLUMN success_value_base64 text,
+    ADD COLUMN success_value_json   jsonb,
+    ADD COLUMN failure              jsonb;
@@ -0,0 +1,25 @@
+-- Every attempt to deploy the code and every removal of the code together with the account.
+-- code_sha256 is hex encoded, the same as in DEPLOY_CONTRACT action args.
+-- code_size is known only for the succeeded deployments
+-- kind options:
+--      {
+--         'DEPLOYMENT',
+--         'DELETION'
+--      }
+CREATE TABLE contract_deployments
+(
+    receipt_id       text           NOT NULL,
+    index_in_receipt integer        NOT NULL,
+    block_timestamp  numeric(20, 0) NOT NULL,
+    account_id       text           NOT NULL,
+    kind             text           NOT NULL,
+    code_sha256      text,
+    code_size        integer,
+    succeeded        boolean        NOT NULL,
+    PRIMARY KEY (receipt_id, index_in_receipt)
+);
+ALTER TABLE contract_deployments
+    ADD CONSTRAINT contract_deployments_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY contract_deployments_block_timestamp_idx ON contract_deployments (block_timestamp);
+CREATE INDEX CONCURRENTLY contract_deployments_account_idx ON contract_deployments (account_id);
+CREATE INDEX CONCURRENTLY contract_deployments_code_sha256_idx ON contract_deployments (code_sha256);
@@ -0,0 +1,21 @@
+-- Contract storage changes, stored only for the accounts from --contract-state-accounts.
+-- value_base64 is NULL when the key is deleted
+CREATE TABLE contract_state_changes
+(
+    account_id           text           NOT NULL,
+    block_timestamp      numeric(20, 0) NOT NULL,
+    block_hash           text           NOT NULL,
+    caused_by_receipt_id text,
+    cause                text           NOT NULL,
+    key_base64           text           NOT NULL,
+    value_base64         text,
+    chunk_index_in_block integer        NOT NULL,
+    index_in_chunk       integer        NOT NULL,
+    PRIMARY KEY (block_timestamp, chunk_index_in_block, index_in_chunk)
+);
+ALTER TABLE contract_state_changes
+    ADD CONSTRAINT contract_state_changes_block_hash_fk FOREIGN KEY (block_hash) REFERENCES blocks (block_hash);
+ALTER TABLE contract_state_changes
+    ADD CONSTRAINT contract_state_changes_receipt_id_fk FOREIGN KEY (caused_by_receipt_id) REFERENCES action_receipts (receipt_id);
+CREATE INDEX CONCURRENTLY contract_state_changes_account_key_idx ON contract_state_changes (account_id, key_base64);
+CREATE INDEX CONCURRENTLY contract_state_changes_block_timestamp_idx ON contract_state_changes (block_timestamp);
@@ -0,0 +1,45 @@
+-- All the state changes with their raw JSON, including the records imported from genesis
+-- change_type options:
+--      {
+--         'ACCOUNT_UPDATE',
+--         'ACCOUNT_DELETION',
+--         'ACCESS_KEY_UPDATE',
+--         'ACCESS_KEY_DELETION',
+--         'DATA_UPDATE',
+--         'DATA_DELETION',
+--         'CONTRACT_CODE_UPDATE',
+--         'CONTRACT_CODE_DELETION'
+--      }
+-- cause options:
+--     {
+--         'NOT_WRITABLE_TO_DISK',
+--         'INITIAL_STATE',
+--         'TRANSACTION_PROCESSING',
+--         'ACTION_RECEIPT_PROCESSING_STARTED',
+--         'ACTION_RECEIPT_GAS_REWARD',
+--         'RECEIPT_PROCESSING',
+--         'POSTPONED_RECEIPT',
+--         'UPDATED_DELAYED_RECEIPTS',
+--         'VALIDATOR_ACCOUNTS_UPDATE',
+--         'MIGRATION',
+--         'RESHARDING'
+--     }
+CREATE TABLE state_changes
+(
+    block_timestamp            numeric(20, 0) NOT NULL,
+    block_hash                 text           NOT NULL,
+    chunk_index_in_block       integer        NOT NULL,
+    index_in_chunk             integer        NOT NULL,
+    change_type                text           NOT NULL,
+    account_id                 text           NOT NULL,
+    cause                      text           NOT NULL,
+    caused_by_transaction_hash text,
+    caused_by_receipt_id       text,
+    raw                        jsonb          NOT NULL,
+    PRIMARY KEY (block_timestamp, chunk_index_in_block, index_in_chunk)
+);
+ALTER TABLE state_changes
+    ADD CONSTRAINT state_changes_block_hash_fk FOREIGN KEY (block_hash) REFERENCES blocks (block_hash);
+CREATE INDEX CONCURRENTLY state_changes_account_idx ON state_changes (account_id);
+CREATE INDEX CONCURRENTLY state_changes_block_timestamp_idx ON state_changes (block_timestamp);
+CREATE INDEX CONCURRENTLY state_changes_change_type_idx ON state_changes (change_type);
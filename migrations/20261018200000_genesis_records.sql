@@ -0,0 +1,5 @@
+-- The accounts and the access keys from genesis are not created by any receipt
+ALTER TABLE accounts
+    ALTER COLUMN created_by_receipt_id DROP NOT NULL;
+ALTER TABLE access_keys
+    ALTER COLUMN created_by_receipt_id DROP NOT NULL;
@@ -0,0 +1,45 @@
+-- The bounds of the epoch are the first and the last indexed blocks of it.
+-- The end of the current epoch moves with every new block
+CREATE TABLE epochs
+(
+    epoch_id              text           NOT NULL,
+    next_epoch_id         text           NOT NULL,
+    start_block_height    numeric(20, 0) NOT NULL,
+    start_block_timestamp numeric(20, 0) NOT NULL,
+    end_block_height      numeric(20, 0) NOT NULL,
+    end_block_timestamp   numeric(20, 0) NOT NULL,
+    PRIMARY KEY (epoch_id)
+);
+CREATE INDEX CONCURRENTLY epochs_start_block_height_idx ON epochs (start_block_height);
+CREATE INDEX CONCURRENTLY epochs_end_block_timestamp_idx ON epochs (end_block_timestamp);
+
+-- Block producers, chunk producers and the accounts with validator proposals of each epoch.
+-- proposed_stake is the last proposal made during the epoch, produced_chunks is the sum over all the shards
+CREATE TABLE validators
+(
+    epoch_id          text           NOT NULL,
+    account_id        text           NOT NULL,
+    produced_blocks   integer        NOT NULL,
+    produced_chunks   integer        NOT NULL,
+    proposed_stake    numeric(38, 0),
+    last_block_height numeric(20, 0) NOT NULL,
+    PRIMARY KEY (epoch_id, account_id)
+);
+ALTER TABLE validators
+    ADD CONSTRAINT validators_epoch_id_fk FOREIGN KEY (epoch_id) REFERENCES epochs (epoch_id);
+CREATE INDEX CONCURRENTLY validators_account_id_idx ON validators (account_id);
+
+-- The chunks produced by each validator in each shard during the epoch.
+-- The expected number of chunks is known only from the epoch info, it doesn't come with the blocks
+CREATE TABLE validator_shards
+(
+    epoch_id          text           NOT NULL,
+    account_id        text           NOT NULL,
+    shard_id          integer        NOT NULL,
+    produced_chunks   integer        NOT NULL,
+    last_block_height numeric(20, 0) NOT NULL,
+    PRIMARY KEY (epoch_id, account_id, shard_id)
+);
+ALTER TABLE validator_shards
+    ADD CONSTRAINT validator_shards_epoch_id_fk FOREIGN KEY (epoch_id) REFERENCES epochs (epoch_id);
+CREATE INDEX CONCURRENTLY validator_shards_account_id_idx ON validator_shards (account_id);
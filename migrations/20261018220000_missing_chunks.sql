@@ -0,0 +1,36 @@
+-- The shards without the new chunk in the block are stored too, chunk_hash is NULL for them.
+-- gas_limit and gas_used of the missing chunk are NULL: the block carries the header of the shard's previous chunk,
+-- and its gas is already counted in the row of that chunk.
+-- The expected producer of the missing chunk is known only from the epoch info, so author_account_id is NULL.
+-- The chunk is identified by the block and the shard now, chunk_hash stays unique for the FKs
+CREATE UNIQUE INDEX CONCURRENTLY chunks_block_hash_index_in_block_uidx ON chunks (block_hash, index_in_block);
+
+ALTER TABLE transactions
+    DROP CONSTRAINT transactions_chunk_hash_fk;
+ALTER TABLE action_receipts
+    DROP CONSTRAINT action_receipts_chunk_hash_fk;
+ALTER TABLE data_receipts
+    DROP CONSTRAINT data_receipts_chunk_hash_fk;
+
+ALTER TABLE chunks
+    DROP CONSTRAINT chunks_pkey;
+ALTER TABLE chunks
+    ADD CONSTRAINT chunks_pkey PRIMARY KEY USING INDEX chunks_block_hash_index_in_block_uidx;
+ALTER TABLE chunks
+    ADD CONSTRAINT chunks_chunk_hash_key UNIQUE (chunk_hash);
+
+ALTER TABLE transactions
+    ADD CONSTRAINT transactions_chunk_hash_fk FOREIGN KEY (chunk_hash) REFERENCES chunks (chunk_hash);
+ALTER TABLE action_receipts
+    ADD CONSTRAINT action_receipts_chunk_hash_fk FOREIGN KEY (chunk_hash) REFERENCES chunks (chunk_hash);
+ALTER TABLE data_receipts
+    ADD CONSTRAINT data_receipts_chunk_hash_fk FOREIGN KEY (chunk_hash) REFERENCES chunks (chunk_hash);
+
+ALTER TABLE chunks
+    ALTER COLUMN chunk_hash DROP NOT NULL,
+    ALTER COLUMN signature DROP NOT NULL,
+    ALTER COLUMN gas_limit DROP NOT NULL,
+    ALTER COLUMN gas_used DROP NOT NULL,
+    ALTER COLUMN author_account_id DROP NOT NULL,
+    ADD COLUMN balance_burnt       numeric(38, 0),
+    ADD COLUMN validator_proposals jsonb;
@@ -0,0 +1,14 @@
+-- The rows stored before this migration have NULLs here until `backfill-blocks` subcommand fills them.
+-- gas_used and gas_limit are the totals of the chunks included in the block
+ALTER TABLE blocks
+    ADD COLUMN epoch_id                text,
+    ADD COLUMN next_epoch_id           text,
+    ADD COLUMN chunks_included         numeric(20, 0),
+    ADD COLUMN validator_reward        numeric(38, 0),
+    ADD COLUMN latest_protocol_version integer,
+    ADD COLUMN block_ordinal           numeric(20, 0),
+    ADD COLUMN random_value            text,
+    ADD COLUMN approvals_count         integer,
+    ADD COLUMN gas_used                numeric(20, 0),
+    ADD COLUMN gas_limit               numeric(20, 0);
+CREATE INDEX CONCURRENTLY blocks_epoch_id_idx ON blocks (epoch_id);
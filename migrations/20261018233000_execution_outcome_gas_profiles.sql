@@ -0,0 +1,21 @@
+-- Filled only with --store-gas-profiles flag. The old execution outcomes don't have the gas profile at all
+-- cost_category options:
+--      {
+--         'ACTION_COST',
+--         'WASM_HOST_COST'
+--      }
+CREATE TABLE execution_outcome_gas_profiles
+(
+    receipt_id          text           NOT NULL,
+    index_in_profile    integer        NOT NULL,
+    block_timestamp     numeric(20, 0) NOT NULL,
+    executor_account_id text           NOT NULL,
+    cost_category       text           NOT NULL,
+    cost                text           NOT NULL,
+    gas_used            numeric(20, 0) NOT NULL,
+    PRIMARY KEY (receipt_id, index_in_profile)
+);
+ALTER TABLE execution_outcome_gas_profiles
+    ADD CONSTRAINT execution_outcome_gas_profiles_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES execution_outcomes (receipt_id);
+CREATE INDEX CONCURRENTLY execution_outcome_gas_profiles_block_timestamp_idx ON execution_outcome_gas_profiles (block_timestamp);
+CREATE INDEX CONCURRENTLY execution_outcome_gas_profiles_executor_idx ON execution_outcome_gas_profiles (executor_account_id);
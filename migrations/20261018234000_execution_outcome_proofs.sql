@@ -0,0 +1,19 @@
+-- The order of the receipt among the receipts produced by the same execution outcome.
+-- It's NULL for the rows stored before this migration
+ALTER TABLE execution_outcomes__receipts
+    ADD COLUMN index_in_outcome integer;
+
+-- Filled only with --store-outcome-proofs flag.
+-- proof is the merkle path of the outcome: [{"hash": "...", "direction": "Left" | "Right"}, ...],
+-- block_hash is the block where the outcome was included
+CREATE TABLE execution_outcome_proofs
+(
+    receipt_id      text           NOT NULL,
+    block_hash      text           NOT NULL,
+    block_timestamp numeric(20, 0) NOT NULL,
+    proof           jsonb          NOT NULL,
+    PRIMARY KEY (receipt_id)
+);
+ALTER TABLE execution_outcome_proofs
+    ADD CONSTRAINT execution_outcome_proofs_receipt_id_fk FOREIGN KEY (receipt_id) REFERENCES execution_outcomes (receipt_id);
+CREATE INDEX CONCURRENTLY execution_outcome_proofs_block_timestamp_idx ON execution_outcome_proofs (block_timestamp);
@@ -0,0 +1,29 @@
+-- The actions as they were signed in the transaction, args have the same format as in action_receipts__actions
+-- action_kind options:
+--      {
+--         'CREATE_ACCOUNT',
+--         'DEPLOY_CONTRACT',
+--         'FUNCTION_CALL',
+--         'TRANSFER',
+--         'STAKE',
+--         'ADD_KEY',
+--         'DELETE_KEY',
+--         'DELETE_ACCOUNT'
+--      }
+CREATE TABLE transaction_actions
+(
+    transaction_hash     text           NOT NULL,
+    index_in_transaction integer        NOT NULL,
+    block_timestamp      numeric(20, 0) NOT NULL,
+    action_kind          text           NOT NULL,
+    args                 jsonb          NOT NULL,
+    signer_account_id    text           NOT NULL,
+    receiver_account_id  text           NOT NULL,
+    PRIMARY KEY (transaction_hash, index_in_transaction)
+);
+ALTER TABLE transaction_actions
+    ADD CONSTRAINT transaction_actions_transaction_hash_fk FOREIGN KEY (transaction_hash) REFERENCES transactions (transaction_hash);
+CREATE INDEX CONCURRENTLY transaction_actions_block_timestamp_idx ON transaction_actions (block_timestamp);
+CREATE INDEX CONCURRENTLY transaction_actions_action_kind_idx ON transaction_actions (action_kind);
+CREATE INDEX CONCURRENTLY transaction_actions_signer_idx ON transaction_actions (signer_account_id);
+CREATE INDEX CONCURRENTLY transaction_actions_receiver_idx ON transaction_actions (receiver_account_id);
@@ -0,0 +1,46 @@
+use futures::StreamExt;
+
+use crate::configs::{self, Opts};
+use crate::{db_adapters, models};
+
+/// Fills the header columns of the blocks which were stored before the columns were added.
+/// Only `blocks` table is updated, the blocks which are not in the database are skipped
+pub(crate) async fn backfill_blocks(
+    opts: &Opts,
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    from_height: u64,
+    to_height: u64,
+) -> anyhow::Result<()> {
+    let db = models::DbHandle {
+        connection: models::DbConnection::Pool(pool),
+        write_mode: configs::WriteMode::Insert,
+    };
+    eprintln!(
+        "Backfilling the blocks from {} to {}",
+        from_height, to_height
+    );
+    let (reader, stream) = opts.streamer(from_height).await?;
+
+    // The stream is dropped after the last block, so the lake stops fetching the next ones
+    let mut updates = tokio_stream::wrappers::ReceiverStream::new(stream)
+        .take_while(|streamer_message| {
+            futures::future::ready(streamer_message.block.header.height <= to_height)
+        })
+        .map(|streamer_message| async move {
+            db_adapters::blocks::update_block_header_columns(db, &streamer_message.block).await?;
+            Ok::<_, anyhow::Error>(streamer_message.block.header.height)
+        })
+        .buffer_unordered(opts.concurrency.get());
+
+    while let Some(block_height) = updates.next().await {
+        let block_height = block_height?;
+        if block_height % 1000 == 0 {
+            eprintln!("{} / {}", block_height, to_height);
+        }
+    }
+    drop(updates);
+    crate::local_lake::wait_reader(reader).await?;
+
+    eprintln!("Backfill up to block {} is finished", to_height);
+    Ok(())
+}
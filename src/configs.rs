@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{ArgEnum, Parser, Subcommand};
 
 /// NEAR Indexer for Explorer
 /// Watches for stream of blocks from the chain
@@ -19,17 +19,181 @@ pub(crate) struct Opts {
     /// Switches indexer to non-strict mode (skips Receipts without parent Transaction hash, puts such block_height into special table)
     #[clap(long)]
     pub non_strict_mode: bool,
-    // todo
-    // /// Store initial data from genesis like Accounts, AccessKeys
-    // #[clap(long)]
-    // pub store_genesis: bool,
-    /// AWS S3 bucket name to get the stream from
+    /// AWS S3 bucket name to get the stream from. Required unless --local-lake-path is set
     #[clap(long)]
-    pub s3_bucket_name: String,
-    /// AWS S3 bucket region
+    pub s3_bucket_name: Option<String>,
+    /// AWS S3 bucket region. Required unless --local-lake-path is set
     #[clap(long)]
-    pub s3_region_name: String,
+    pub s3_region_name: Option<String>,
+    /// Custom S3 endpoint, e.g. MinIO mirror of the lake bucket. If None, AWS S3 is used.
+    /// The objects are always requested with path-style addressing (`<endpoint>/<bucket>/<key>`):
+    /// aws-sdk-s3 0.11 has no virtual-hosted style for the custom endpoint, so there is no flag to switch it
+    #[clap(long)]
+    pub s3_endpoint: Option<http::Uri>,
+    /// Static access key for S3. If None, the credentials are taken from the environment and ~/.aws
+    #[clap(long, env)]
+    pub s3_access_key_id: Option<String>,
+    /// Static secret key for S3, goes together with --s3-access-key-id
+    #[clap(long, env, hide_env_values = true)]
+    pub s3_secret_access_key: Option<String>,
+    /// Local folder laid out like the lake bucket (`<block_height>/block.json`, `shard_N.json`)
+    /// to read the blocks from instead of AWS S3
+    #[clap(long)]
+    pub local_lake_path: Option<std::path::PathBuf>,
     /// Block height to start the stream from. If None, start from interruption
     #[clap(long, short)]
     pub start_block_height: Option<u64>,
+    /// Writes all the rows of N consecutive blocks through one database transaction,
+    /// so the readers never see partially stored blocks. If None, the rows are written directly
+    #[clap(long)]
+    pub blocks_per_transaction: Option<u64>,
+    /// Number of blocks handled concurrently. Receipts and ExecutionOutcomes are still stored
+    /// in the order of the blocks. Ignored with --blocks-per-transaction
+    #[clap(long, default_value = "1")]
+    pub concurrency: std::num::NonZeroUsize,
+    /// Port to serve Prometheus metrics at `/metrics`. Metrics are not served if not set
+    #[clap(long)]
+    pub metrics_port: Option<u16>,
+    /// The way the rows are written to the database.
+    /// `copy` streams the rows with COPY through staging tables, it is much faster for backfills
+    #[clap(long, arg_enum, default_value = "insert")]
+    pub write_mode: WriteMode,
+    /// Comma separated contract accounts which storage changes are stored
+    /// to `contract_state_changes` table. Nothing is stored if not set
+    #[clap(long, use_value_delimiter = true)]
+    pub contract_state_accounts: Vec<String>,
+    /// Stores the gas used by each cost of the execution outcomes to `execution_outcome_gas_profiles` table
+    #[clap(long)]
+    pub store_gas_profiles: bool,
+    /// Stores the merkle proofs of the execution outcomes to `execution_outcome_proofs` table
+    #[clap(long)]
+    pub store_outcome_proofs: bool,
+    #[clap(subcommand)]
+    pub command: Option<SubCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum SubCommand {
+    /// Re-indexes the blocks from `_blocks_to_rerun` table and removes the ones which are complete now
+    Rerun,
+    /// Removes all the data starting from the given block height and moves the checkpoint back,
+    /// so the indexer continues from this height
+    Rollback {
+        /// The first block height to remove
+        #[clap(long)]
+        to_height: u64,
+    },
+    /// Stores the accounts, the access keys and the contract codes from genesis.
+    /// The genesis block should be indexed before the import
+    ImportGenesis {
+        /// Path to genesis.json or to the JSON array of genesis records
+        #[clap(long)]
+        genesis_file: std::path::PathBuf,
+        /// Genesis block height. Required for the file with genesis records, overrides the value from genesis.json
+        #[clap(long)]
+        genesis_height: Option<u64>,
+    },
+    /// Fills the columns of `blocks` table which were added after the blocks were stored.
+    /// Nothing else is changed
+    BackfillBlocks {
+        /// The first block height to update
+        #[clap(long)]
+        from_height: u64,
+        /// The last block height to update
+        #[clap(long)]
+        to_height: u64,
+    },
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug)]
+pub enum WriteMode {
+    /// Batched `INSERT ... VALUES` queries
+    Insert,
+    /// `COPY ... FROM STDIN (FORMAT binary)` to the staging tables merged into the destination ones
+    Copy,
+}
+
+impl Opts {
+    /// Starts the stream of blocks from the local folder if it is set, or from the lake otherwise.
+    /// The local folder reader is returned as well, see `local_lake::wait_reader`
+    pub(crate) async fn streamer(
+        &self,
+        start_block_height: u64,
+    ) -> anyhow::Result<(
+        Option<tokio::task::JoinHandle<anyhow::Result<()>>>,
+        tokio::sync::mpsc::Receiver<near_indexer_primitives::StreamerMessage>,
+    )> {
+        Ok(match &self.local_lake_path {
+            Some(local_lake_path) => {
+                let (reader, stream) =
+                    crate::local_lake::streamer(local_lake_path.clone(), start_block_height);
+                (Some(reader), stream)
+            }
+            None => (
+                None,
+                near_lake_framework::streamer(self.lake_config(start_block_height).await?),
+            ),
+        })
+    }
+
+    /// Builds the lake config for the stream starting from the given block height
+    pub(crate) async fn lake_config(
+        &self,
+        start_block_height: u64,
+    ) -> anyhow::Result<near_lake_framework::LakeConfig> {
+        let s3_region_name = self
+            .s3_region_name
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("--s3-region-name is required"))?;
+
+        Ok(near_lake_framework::LakeConfig {
+            s3_config: self.s3_config(&s3_region_name).await?,
+            s3_bucket_name: self
+                .s3_bucket_name
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("--s3-bucket-name is required"))?,
+            s3_region_name,
+            start_block_height,
+        })
+    }
+
+    /// Builds S3 config for the custom endpoint and static credentials.
+    /// If none of them are set, the lake builds the default AWS config by itself
+    async fn s3_config(
+        &self,
+        s3_region_name: &str,
+    ) -> anyhow::Result<Option<aws_sdk_s3::config::Config>> {
+        let credentials = match (&self.s3_access_key_id, &self.s3_secret_access_key) {
+            (Some(access_key_id), Some(secret_access_key)) => Some(aws_sdk_s3::Credentials::new(
+                access_key_id,
+                secret_access_key,
+                None,
+                None,
+                "indexer-base",
+            )),
+            (None, None) => None,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "--s3-access-key-id and --s3-secret-access-key should be set together"
+                ))
+            }
+        };
+        if self.s3_endpoint.is_none() && credentials.is_none() {
+            return Ok(None);
+        }
+
+        let mut shared_config_loader =
+            aws_config::from_env().region(aws_sdk_s3::Region::new(s3_region_name.to_string()));
+        if let Some(credentials) = credentials {
+            shared_config_loader = shared_config_loader.credentials_provider(credentials);
+        }
+        let shared_config = shared_config_loader.load().await;
+
+        let mut s3_config_builder = aws_sdk_s3::config::Builder::from(&shared_config);
+        if let Some(s3_endpoint) = &self.s3_endpoint {
+            s3_config_builder = s3_config_builder
+                .endpoint_resolver(aws_sdk_s3::Endpoint::immutable(s3_endpoint.clone()));
+        }
+        Ok(Some(s3_config_builder.build()))
+    }
 }
@@ -0,0 +1,99 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models;
+
+/// Adds the rows for the created keys and closes the rows of the deleted ones.
+/// Only the changes caused by receipts are taken: AddKey, DeleteKey, DeleteAccount actions
+/// and the transfers creating implicit accounts. Transactions only update the nonce of the key
+pub(crate) async fn store_access_keys(
+    db: models::DbHandle<'_>,
+    shards: &[near_indexer_primitives::IndexerShard],
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let mut created_access_keys: Vec<models::AccessKey> = vec![];
+    // The keys created in the previous blocks, we close their rows with UPDATE
+    let mut deleted_access_keys: Vec<(String, String, String)> = vec![];
+
+    for state_change_with_cause in shards.iter().flat_map(|shard| shard.state_changes.iter()) {
+        let receipt_id = match state_change_with_cause.cause {
+            near_indexer_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                receipt_hash,
+            } => receipt_hash,
+            _ => continue,
+        };
+
+        match &state_change_with_cause.value {
+            near_indexer_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                account_id,
+                public_key,
+                access_key,
+            } => created_access_keys.push(models::AccessKey::from_access_key_view(
+                public_key.to_string(),
+                account_id.to_string(),
+                access_key,
+                Some(&receipt_id),
+                block_timestamp,
+            )),
+            near_indexer_primitives::views::StateChangeValueView::AccessKeyDeletion {
+                account_id,
+                public_key,
+            } => {
+                let (account_id, public_key) = (account_id.to_string(), public_key.to_string());
+                // The key could be created earlier in the same block
+                match created_access_keys.iter_mut().find(|access_key| {
+                    access_key.account_id == account_id
+                        && access_key.public_key == public_key
+                        && access_key.deleted_by_receipt_id.is_none()
+                }) {
+                    Some(access_key) => {
+                        access_key.deleted_by_receipt_id = Some(receipt_id.to_string());
+                        access_key.deleted_by_block_timestamp = Some(block_timestamp.into());
+                    }
+                    None => {
+                        deleted_access_keys.push((account_id, public_key, receipt_id.to_string()))
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Deletions go first: they refer only to the keys from the previous blocks
+    for (account_id, public_key, receipt_id) in deleted_access_keys {
+        let mut args = sqlx::postgres::PgArguments::default();
+        args.add(receipt_id);
+        args.add(BigDecimal::from(block_timestamp));
+        args.add(account_id);
+        args.add(public_key);
+        let query = "UPDATE access_keys
+                     SET deleted_by_receipt_id = $1, deleted_by_block_timestamp = $2
+                     WHERE account_id = $3 AND public_key = $4
+                         AND deleted_by_receipt_id IS NULL AND created_by_block_timestamp < $2";
+        db.execute(query, args).await?;
+    }
+
+    created_access_keys
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, access_key)| {
+            access_key.index_in_block = i as i32;
+        });
+    models::chunked_insert(db, &created_access_keys).await?;
+
+    Ok(())
+}
+
+/// Reopens the keys which were deleted starting from the given block timestamp
+pub(crate) async fn restore_access_keys_deleted_from(
+    db: models::DbHandle<'_>,
+    block_timestamp: &BigDecimal,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    let query = "UPDATE access_keys
+                 SET deleted_by_receipt_id = NULL, deleted_by_block_timestamp = NULL
+                 WHERE deleted_by_block_timestamp >= $1";
+    db.execute(query, args).await?;
+    Ok(())
+}
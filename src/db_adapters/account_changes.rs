@@ -3,14 +3,14 @@ use futures::future::try_join_all;
 use crate::models;
 
 pub(crate) async fn store_account_changes(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     shards: &[near_indexer_primitives::IndexerShard],
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
 ) -> anyhow::Result<()> {
     let futures = shards.iter().map(|shard| {
         store_account_changes_for_chunk(
-            pool,
+            db,
             &shard.state_changes,
             block_hash,
             block_timestamp,
@@ -22,14 +22,14 @@ pub(crate) async fn store_account_changes(
 }
 
 async fn store_account_changes_for_chunk(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     state_changes: &near_indexer_primitives::views::StateChangesView,
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
     shard_id: near_indexer_primitives::types::ShardId,
 ) -> anyhow::Result<()> {
     models::chunked_insert(
-        pool,
+        db,
         &state_changes
             .iter()
             .filter_map(|state_change| {
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models;
+
+/// Adds the rows for the created accounts and closes the rows of the deleted ones.
+/// We take CREATE_ACCOUNT and DELETE_ACCOUNT actions from the receipts executed in this block,
+/// because only the execution outcome tells us whether the actions were applied.
+/// Implicit accounts have no CREATE_ACCOUNT, they are created by the TRANSFER to them
+pub(crate) async fn store_accounts(
+    db: models::DbHandle<'_>,
+    shards: &[near_indexer_primitives::IndexerShard],
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let mut created_accounts: Vec<models::Account> = vec![];
+    let mut implicit_account_creations = implicit_account_creations(shards);
+    // The accounts created in the previous blocks, we close their rows with UPDATE
+    let mut deleted_accounts: Vec<(String, String, String)> = vec![];
+
+    for outcome in shards
+        .iter()
+        .flat_map(|shard| shard.receipt_execution_outcomes.iter())
+    {
+        if !crate::db_adapters::is_successful(outcome) {
+            continue;
+        }
+        let receipt = match &outcome.receipt {
+            Some(receipt) => receipt,
+            None => continue,
+        };
+        let actions = match &receipt.receipt {
+            near_indexer_primitives::views::ReceiptEnumView::Action { actions, .. } => actions,
+            near_indexer_primitives::views::ReceiptEnumView::Data { .. } => continue,
+        };
+
+        for action in actions {
+            match action {
+                near_indexer_primitives::views::ActionView::CreateAccount => {
+                    created_accounts.push(models::Account::new_created(
+                        receipt.receiver_id.to_string(),
+                        Some(&receipt.receipt_id),
+                        block_timestamp,
+                    ))
+                }
+                // The receipt could have several transfers, the account is created by the first one
+                near_indexer_primitives::views::ActionView::Transfer { .. }
+                    if implicit_account_creations.remove(&(
+                        receipt.receiver_id.to_string(),
+                        receipt.receipt_id.to_string(),
+                    )) =>
+                {
+                    created_accounts.push(models::Account::new_created(
+                        receipt.receiver_id.to_string(),
+                        Some(&receipt.receipt_id),
+                        block_timestamp,
+                    ))
+                }
+                near_indexer_primitives::views::ActionView::DeleteAccount { beneficiary_id } => {
+                    let account_id = receipt.receiver_id.to_string();
+                    // The account could be created earlier in the same block
+                    match created_accounts.iter_mut().find(|account| {
+                        account.account_id == account_id && account.deleted_by_receipt_id.is_none()
+                    }) {
+                        Some(account) => {
+                            account.deleted_by_receipt_id = Some(receipt.receipt_id.to_string());
+                            account.deleted_by_block_timestamp = Some(block_timestamp.into());
+                            account.beneficiary_account_id = Some(beneficiary_id.to_string());
+                        }
+                        None => deleted_accounts.push((
+                            account_id,
+                            receipt.receipt_id.to_string(),
+                            beneficiary_id.to_string(),
+                        )),
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Deletions go first: they refer only to the accounts from the previous blocks
+    for (account_id, receipt_id, beneficiary_account_id) in deleted_accounts {
+        let mut args = sqlx::postgres::PgArguments::default();
+        args.add(receipt_id);
+        args.add(BigDecimal::from(block_timestamp));
+        args.add(beneficiary_account_id);
+        args.add(account_id);
+        let query = "UPDATE accounts
+                     SET deleted_by_receipt_id = $1, deleted_by_block_timestamp = $2, beneficiary_account_id = $3
+                     WHERE account_id = $4
+                         AND deleted_by_receipt_id IS NULL AND created_by_block_timestamp < $2";
+        db.execute(query, args).await?;
+    }
+
+    created_accounts
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, account)| {
+            account.index_in_block = i as i32;
+        });
+    models::chunked_insert(db, &created_accounts).await?;
+
+    Ok(())
+}
+
+/// The transfer to the implicit account which doesn't exist creates it together with the full access key
+/// of the public key the account id is made from. The later transfers don't touch the access keys,
+/// so this key tells us that the receipt created the account. Returns (account_id, receipt_id) pairs
+fn implicit_account_creations(
+    shards: &[near_indexer_primitives::IndexerShard],
+) -> HashSet<(String, String)> {
+    shards
+        .iter()
+        .flat_map(|shard| shard.state_changes.iter())
+        .filter_map(
+            |state_change| match (&state_change.cause, &state_change.value) {
+                (
+                    near_indexer_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                        receipt_hash,
+                    },
+                    near_indexer_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                        account_id,
+                        public_key,
+                        ..
+                    },
+                ) => {
+                    let account_id = account_id.to_string();
+                    let is_implicit_account_key = account_id.len() == 64
+                        && hex::decode(&account_id)
+                            .map_or(false, |key_data| key_data == public_key.key_data());
+                    is_implicit_account_key.then(|| (account_id, receipt_hash.to_string()))
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Reopens the accounts which were deleted starting from the given block timestamp
+pub(crate) async fn restore_accounts_deleted_from(
+    db: models::DbHandle<'_>,
+    block_timestamp: &BigDecimal,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    let query = "UPDATE accounts
+                 SET deleted_by_receipt_id = NULL, deleted_by_block_timestamp = NULL, beneficiary_account_id = NULL
+                 WHERE deleted_by_block_timestamp >= $1";
+    db.execute(query, args).await?;
+    Ok(())
+}
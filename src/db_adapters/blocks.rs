@@ -1,9 +1,38 @@
+use sqlx::Arguments;
+
 use crate::models;
 
 pub(crate) async fn store_block(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
+    block: &near_indexer_primitives::views::BlockView,
+) -> anyhow::Result<()> {
+    models::chunked_insert(db, &vec![models::Block::from_block_view(block)]).await?;
+    Ok(())
+}
+
+/// Fills the header columns of the block which was stored before they were added
+pub(crate) async fn update_block_header_columns(
+    db: models::DbHandle<'_>,
     block: &near_indexer_primitives::views::BlockView,
 ) -> anyhow::Result<()> {
-    models::chunked_insert(pool, &vec![models::Block::from_block_view(block)]).await?;
+    let block = models::Block::from_block_view(block);
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block.epoch_id);
+    args.add(block.next_epoch_id);
+    args.add(block.chunks_included);
+    args.add(block.validator_reward);
+    args.add(block.latest_protocol_version);
+    args.add(block.block_ordinal);
+    args.add(block.random_value);
+    args.add(block.approvals_count);
+    args.add(block.gas_used);
+    args.add(block.gas_limit);
+    args.add(block.block_hash);
+    let query = "UPDATE blocks
+                 SET epoch_id = $1, next_epoch_id = $2, chunks_included = $3, validator_reward = $4,
+                     latest_protocol_version = $5, block_ordinal = $6, random_value = $7,
+                     approvals_count = $8, gas_used = $9, gas_limit = $10
+                 WHERE block_hash = $11";
+    db.execute(query, args).await?;
     Ok(())
 }
@@ -0,0 +1,64 @@
+use bigdecimal::BigDecimal;
+use num_traits::ToPrimitive;
+use sqlx::{Arguments, Row};
+
+use crate::models;
+
+/// Returns the heights of the blocks with Receipts which didn't find their parent Transaction
+/// during the indexing in non-strict mode
+pub(crate) async fn get_blocks_to_rerun(db: models::DbHandle<'_>) -> anyhow::Result<Vec<u64>> {
+    let query = "SELECT block_height FROM _blocks_to_rerun ORDER BY block_height";
+    let res = models::select_retry_or_panic(db, query, &[]).await?;
+    res.iter()
+        .map(|row| {
+            row.get::<BigDecimal, _>(0)
+                .to_u64()
+                .ok_or_else(|| anyhow::anyhow!("`block_height` expected to be u64"))
+        })
+        .collect()
+}
+
+/// Checks that all the given receipts are stored, which means all of them found their parent Transaction
+pub(crate) async fn are_all_receipts_stored(
+    db: models::DbHandle<'_>,
+    receipt_ids: &[String],
+) -> anyhow::Result<bool> {
+    if receipt_ids.is_empty() {
+        return Ok(true);
+    }
+
+    // Both parts use the same substitution items
+    let placeholder = models::create_placeholder(&mut 1, receipt_ids.len())?;
+    let query = format!(
+        "SELECT receipt_id FROM action_receipts WHERE receipt_id IN {0}
+         UNION ALL
+         SELECT receipt_id FROM data_receipts WHERE receipt_id IN {0}",
+        placeholder
+    );
+
+    let res = models::select_retry_or_panic(db, &query, receipt_ids).await?;
+    Ok(res.len() == receipt_ids.len())
+}
+
+pub(crate) async fn delete_block_to_rerun(
+    db: models::DbHandle<'_>,
+    block_height: u64,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(BigDecimal::from(block_height));
+    let query = "DELETE FROM _blocks_to_rerun WHERE block_height = $1";
+    db.execute(query, args).await?;
+    Ok(())
+}
+
+/// Forgets about the blocks starting from the given height, they are going to be indexed again
+pub(crate) async fn delete_blocks_to_rerun_from(
+    db: models::DbHandle<'_>,
+    block_height: u64,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(BigDecimal::from(block_height));
+    let query = "DELETE FROM _blocks_to_rerun WHERE block_height >= $1";
+    db.execute(query, args).await?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use bigdecimal::BigDecimal;
+use num_traits::ToPrimitive;
+use sqlx::{Arguments, Row};
+
+use crate::models;
+
+/// Saves the height of the block which is fully stored in all the tables
+pub(crate) async fn update_last_processed_block_height(
+    db: models::DbHandle<'_>,
+    block_height: u64,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(crate::INDEXER_ID);
+    args.add(BigDecimal::from(block_height));
+    let query = "INSERT INTO _checkpoints VALUES ($1, $2)
+                 ON CONFLICT (indexer_id) DO UPDATE SET last_processed_block_height = EXCLUDED.last_processed_block_height";
+    db.execute(query, args).await?;
+    Ok(())
+}
+
+/// Returns the height of the last fully stored block, None if the indexer has never run before
+pub(crate) async fn get_last_processed_block_height(
+    db: models::DbHandle<'_>,
+) -> anyhow::Result<Option<u64>> {
+    let query = "SELECT last_processed_block_height FROM _checkpoints WHERE indexer_id = $1";
+    let res = models::select_retry_or_panic(db, query, &[crate::INDEXER_ID.to_string()]).await?;
+    res.first()
+        .map(|row| {
+            row.get::<BigDecimal, _>(0)
+                .to_u64()
+                .ok_or_else(|| anyhow::anyhow!("`last_processed_block_height` expected to be u64"))
+        })
+        .transpose()
+}
+
+/// Moves the checkpoint back to the given height. The checkpoint which is already lower stays untouched
+pub(crate) async fn rollback_last_processed_block_height(
+    db: models::DbHandle<'_>,
+    block_height: u64,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(crate::INDEXER_ID);
+    args.add(BigDecimal::from(block_height));
+    let query = "UPDATE _checkpoints SET last_processed_block_height = $2
+                 WHERE indexer_id = $1 AND last_processed_block_height > $2";
+    db.execute(query, args).await?;
+    Ok(())
+}
@@ -1,20 +1,25 @@
 use crate::models;
 
 pub(crate) async fn store_chunks(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
+    block: &near_indexer_primitives::views::BlockView,
     shards: &[near_indexer_primitives::IndexerShard],
-    block_hash: &near_indexer_primitives::CryptoHash,
-    block_timestamp: u64,
 ) -> anyhow::Result<()> {
     models::chunked_insert(
-        pool,
+        db,
         &shards
             .iter()
-            .filter_map(|shard| {
-                shard
-                    .chunk
-                    .as_ref()
-                    .map(|chunk| models::Chunk::from_chunk_view(chunk, block_hash, block_timestamp))
+            .map(|shard| match &shard.chunk {
+                Some(chunk) => models::Chunk::from_chunk_view(
+                    chunk,
+                    &block.header.hash,
+                    block.header.timestamp,
+                ),
+                None => models::Chunk::missing(
+                    shard.shard_id,
+                    &block.header.hash,
+                    block.header.timestamp,
+                ),
             })
             .collect::<Vec<models::Chunk>>(),
     )
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use crate::models;
+
+/// Saves DEPLOY_CONTRACT actions of the receipts executed in this block together with
+/// the result of the execution, and the removals of the code caused by DELETE_ACCOUNT
+pub(crate) async fn store_contract_deployments(
+    db: models::DbHandle<'_>,
+    shards: &[near_indexer_primitives::IndexerShard],
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let mut contract_deployments: Vec<models::ContractDeployment> = vec![];
+
+    for shard in shards {
+        // The size of the deployed code, by the receipt and the account
+        let mut code_sizes: HashMap<(near_indexer_primitives::CryptoHash, String), usize> =
+            HashMap::new();
+        for state_change_with_cause in &shard.state_changes {
+            let receipt_id = match state_change_with_cause.cause {
+                near_indexer_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                    receipt_hash,
+                } => receipt_hash,
+                _ => continue,
+            };
+            match &state_change_with_cause.value {
+                near_indexer_primitives::views::StateChangeValueView::ContractCodeUpdate {
+                    account_id,
+                    code,
+                } => {
+                    code_sizes.insert((receipt_id, account_id.to_string()), code.len());
+                }
+                near_indexer_primitives::views::StateChangeValueView::ContractCodeDeletion {
+                    account_id,
+                } => contract_deployments.push(
+                    models::ContractDeployment::from_contract_code_deletion(
+                        &receipt_id,
+                        account_id,
+                        block_timestamp,
+                    ),
+                ),
+                _ => {}
+            }
+        }
+
+        for outcome in &shard.receipt_execution_outcomes {
+            let receipt = match &outcome.receipt {
+                Some(receipt) => receipt,
+                None => continue,
+            };
+            let actions = match &receipt.receipt {
+                near_indexer_primitives::views::ReceiptEnumView::Action { actions, .. } => actions,
+                near_indexer_primitives::views::ReceiptEnumView::Data { .. } => continue,
+            };
+            let succeeded = crate::db_adapters::is_successful(outcome);
+
+            for action in actions {
+                if let near_indexer_primitives::views::ActionView::DeployContract { code } = action
+                {
+                    let code_size = code_sizes
+                        .get(&(receipt.receipt_id, receipt.receiver_id.to_string()))
+                        .copied()
+                        .filter(|_| succeeded);
+                    contract_deployments.push(
+                        models::ContractDeployment::from_deploy_contract_action(
+                            receipt,
+                            code,
+                            code_size,
+                            succeeded,
+                            block_timestamp,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    let mut indexes_in_receipts: HashMap<String, i32> = HashMap::new();
+    for contract_deployment in contract_deployments.iter_mut() {
+        let index_in_receipt = indexes_in_receipts
+            .entry(contract_deployment.receipt_id.clone())
+            .or_insert(0);
+        contract_deployment.index_in_receipt = *index_in_receipt;
+        *index_in_receipt += 1;
+    }
+
+    models::chunked_insert(db, &contract_deployments).await?;
+
+    Ok(())
+}
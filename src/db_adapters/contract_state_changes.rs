@@ -0,0 +1,63 @@
+use futures::future::try_join_all;
+
+use crate::models;
+
+/// Saves the storage changes of the given contract accounts, nothing is saved if the list is empty
+pub(crate) async fn store_contract_state_changes(
+    db: models::DbHandle<'_>,
+    shards: &[near_indexer_primitives::IndexerShard],
+    block_hash: &near_indexer_primitives::CryptoHash,
+    block_timestamp: u64,
+    contract_state_accounts: &[String],
+) -> anyhow::Result<()> {
+    if contract_state_accounts.is_empty() {
+        return Ok(());
+    }
+
+    let futures = shards.iter().map(|shard| {
+        store_contract_state_changes_for_chunk(
+            db,
+            &shard.state_changes,
+            block_hash,
+            block_timestamp,
+            shard.shard_id,
+            contract_state_accounts,
+        )
+    });
+
+    try_join_all(futures).await.map(|_| ())
+}
+
+async fn store_contract_state_changes_for_chunk(
+    db: models::DbHandle<'_>,
+    state_changes: &near_indexer_primitives::views::StateChangesView,
+    block_hash: &near_indexer_primitives::CryptoHash,
+    block_timestamp: u64,
+    shard_id: near_indexer_primitives::types::ShardId,
+    contract_state_accounts: &[String],
+) -> anyhow::Result<()> {
+    models::chunked_insert(
+        db,
+        &state_changes
+            .iter()
+            // The index goes through all the state changes of the chunk,
+            // so it doesn't depend on the allowed accounts and matches `state_changes` table
+            .enumerate()
+            .filter_map(|(index_in_chunk, state_change)| {
+                models::ContractStateChange::from_state_change_with_cause(
+                    state_change,
+                    block_hash,
+                    block_timestamp,
+                    shard_id as i32,
+                    index_in_chunk as i32,
+                )
+            })
+            .filter(|contract_state_change| {
+                contract_state_accounts.contains(&contract_state_change.account_id)
+            })
+            .collect::<Vec<models::ContractStateChange>>(),
+    )
+    .await?;
+
+    Ok(())
+}
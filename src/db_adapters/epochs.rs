@@ -0,0 +1,104 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::configs;
+use crate::models;
+
+/// Widens the bounds of the block's epoch and adds the produced blocks and chunks to the validators.
+/// The blocks have to come here in their order, otherwise some of them are not counted
+pub(crate) async fn store_epoch_and_validators(
+    db: models::DbHandle<'_>,
+    block: &near_indexer_primitives::views::BlockView,
+    shards: &[near_indexer_primitives::IndexerShard],
+) -> anyhow::Result<()> {
+    // COPY skips the existing rows, and we need to update them
+    let db = models::DbHandle {
+        write_mode: configs::WriteMode::Insert,
+        ..db
+    };
+
+    models::chunked_insert(db, &[models::Epoch::from_block_view(block)]).await?;
+    // FK to epoch_id
+    models::chunked_insert(db, &models::Validator::from_block(block, shards)).await?;
+    models::chunked_insert(db, &models::ValidatorShard::from_block(block, shards)).await?;
+
+    Ok(())
+}
+
+/// Moves the end of the epochs back to the last block before the given block timestamp
+/// and recounts their validators by the blocks and the chunks which stay in the database.
+/// The last stake proposal is taken again from the chunks, the block's proposals are the ones of its new chunks.
+/// The epochs started from the given block timestamp should be already removed
+pub(crate) async fn restore_epochs_ended_from(
+    db: models::DbHandle<'_>,
+    block_timestamp: &BigDecimal,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    let query = "UPDATE validators
+                 SET produced_blocks = (
+                         SELECT count(*) FROM blocks
+                         WHERE blocks.author_account_id = validators.account_id
+                             AND blocks.block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND blocks.block_timestamp < $1
+                     ),
+                     produced_chunks = (
+                         SELECT count(*) FROM chunks JOIN blocks ON blocks.block_hash = chunks.block_hash
+                         WHERE chunks.author_account_id = validators.account_id
+                             AND blocks.block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND blocks.block_timestamp < $1
+                     ),
+                     proposed_stake = (
+                         SELECT (proposal ->> 'stake')::numeric
+                         FROM chunks
+                             JOIN blocks ON blocks.block_hash = chunks.block_hash
+                             CROSS JOIN jsonb_array_elements(chunks.validator_proposals) AS proposal
+                         WHERE proposal ->> 'account_id' = validators.account_id
+                             AND blocks.block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND blocks.block_timestamp < $1
+                         ORDER BY blocks.block_height DESC, chunks.index_in_block DESC
+                         LIMIT 1
+                     ),
+                     last_block_height = (
+                         SELECT max(block_height) FROM blocks
+                         WHERE block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND block_timestamp < $1
+                     )
+                 FROM epochs
+                 WHERE validators.epoch_id = epochs.epoch_id AND epochs.end_block_timestamp >= $1";
+    db.execute(query, args).await?;
+
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    let query = "UPDATE validator_shards
+                 SET produced_chunks = (
+                         SELECT count(*) FROM chunks JOIN blocks ON blocks.block_hash = chunks.block_hash
+                         WHERE chunks.author_account_id = validator_shards.account_id
+                             AND chunks.index_in_block = validator_shards.shard_id
+                             AND blocks.block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND blocks.block_timestamp < $1
+                     ),
+                     last_block_height = (
+                         SELECT max(block_height) FROM blocks
+                         WHERE block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                             AND block_timestamp < $1
+                     )
+                 FROM epochs
+                 WHERE validator_shards.epoch_id = epochs.epoch_id AND epochs.end_block_timestamp >= $1";
+    db.execute(query, args).await?;
+
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    let query = "UPDATE epochs
+                 SET (end_block_height, end_block_timestamp) = (
+                     SELECT block_height, block_timestamp FROM blocks
+                     WHERE block_height BETWEEN epochs.start_block_height AND epochs.end_block_height
+                         AND block_timestamp < $1
+                     ORDER BY block_height DESC
+                     LIMIT 1
+                 )
+                 WHERE end_block_timestamp >= $1";
+    db.execute(query, args).await?;
+
+    Ok(())
+}
@@ -1,40 +1,46 @@
 use cached::Cached;
 use futures::future::try_join_all;
+use futures::try_join;
 
+use crate::configs::Opts;
 use crate::models;
 
 pub(crate) async fn store_execution_outcomes(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     shards: &[near_indexer_primitives::IndexerShard],
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
     receipts_cache: crate::ReceiptsCache,
+    opts: &Opts,
 ) -> anyhow::Result<()> {
     let futures = shards.iter().map(|shard| {
         store_execution_outcomes_for_chunk(
-            pool,
+            db,
             &shard.receipt_execution_outcomes,
             shard.shard_id,
             block_hash,
             block_timestamp,
             receipts_cache.clone(),
+            opts,
         )
     });
 
     try_join_all(futures).await.map(|_| ())
 }
 
-/// Saves ExecutionOutcome to database and then saves ExecutionOutcomesReceipts
+/// Saves ExecutionOutcome to database and then saves ExecutionOutcomesReceipts,
+/// the logs, the events from them and optionally the gas profiles and the proofs
 pub async fn store_execution_outcomes_for_chunk(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     execution_outcomes: &[near_indexer_primitives::IndexerExecutionOutcomeWithReceipt],
     shard_id: near_indexer_primitives::types::ShardId,
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
     receipts_cache: crate::ReceiptsCache,
+    opts: &Opts,
 ) -> anyhow::Result<()> {
     models::chunked_insert(
-        pool,
+        db,
         &execution_outcomes
             .iter()
             .enumerate()
@@ -60,29 +66,36 @@ pub async fn store_execution_outcomes_for_chunk(
             &crate::ReceiptOrDataId::ReceiptId(outcome.execution_outcome.id),
         );
 
-        outcome_receipt_models.extend(outcome.execution_outcome.outcome.receipt_ids.iter().map(
-            |receipt_id| {
-                // if we have `parent_transaction_hash` from cache, then we put all "produced" Receipt IDs
-                // as key and `parent_transaction_hash` as value, so the Receipts from one of the next blocks
-                // could find their parents in cache
-                if let Some(transaction_hash) = &parent_transaction_hash {
-                    receipts_cache_lock.cache_set(
-                        crate::ReceiptOrDataId::ReceiptId(*receipt_id),
-                        transaction_hash.clone(),
-                    );
-                }
+        outcome_receipt_models.extend(
+            outcome
+                .execution_outcome
+                .outcome
+                .receipt_ids
+                .iter()
+                .enumerate()
+                .map(|(index_in_outcome, receipt_id)| {
+                    // if we have `parent_transaction_hash` from cache, then we put all "produced" Receipt IDs
+                    // as key and `parent_transaction_hash` as value, so the Receipts from one of the next blocks
+                    // could find their parents in cache
+                    if let Some(transaction_hash) = &parent_transaction_hash {
+                        receipts_cache_lock.cache_set(
+                            crate::ReceiptOrDataId::ReceiptId(*receipt_id),
+                            transaction_hash.clone(),
+                        );
+                    }
 
-                models::ExecutionOutcomeReceipt {
-                    block_hash: block_hash.to_string(),
-                    block_timestamp: block_timestamp.into(),
-                    executed_receipt_id: outcome.execution_outcome.id.to_string(),
-                    produced_receipt_id: receipt_id.to_string(),
-                    chunk_index_in_block: shard_id as i32,
-                    // we fill it later because we need flatmap result
-                    index_in_chunk: 0,
-                }
-            },
-        ));
+                    models::ExecutionOutcomeReceipt {
+                        block_hash: block_hash.to_string(),
+                        block_timestamp: block_timestamp.into(),
+                        executed_receipt_id: outcome.execution_outcome.id.to_string(),
+                        produced_receipt_id: receipt_id.to_string(),
+                        chunk_index_in_block: shard_id as i32,
+                        // we fill it later because we need flatmap result
+                        index_in_chunk: 0,
+                        index_in_outcome: index_in_outcome as i32,
+                    }
+                }),
+        );
     }
     drop(receipts_cache_lock);
 
@@ -93,7 +106,63 @@ pub async fn store_execution_outcomes_for_chunk(
             execution_outcomes_receipt.index_in_chunk = i as i32;
         });
 
-    models::chunked_insert(pool, &outcome_receipt_models).await?;
+    let outcome_log_models: Vec<models::ExecutionOutcomeLog> = execution_outcomes
+        .iter()
+        .flat_map(|outcome| {
+            outcome
+                .execution_outcome
+                .outcome
+                .logs
+                .iter()
+                .enumerate()
+                .map(|(log_index, log)| {
+                    models::ExecutionOutcomeLog::from_log(
+                        &outcome.execution_outcome,
+                        log,
+                        log_index as i32,
+                        block_timestamp,
+                    )
+                })
+        })
+        .collect();
+
+    let outcome_gas_profile_models: Vec<models::ExecutionOutcomeGasProfile> =
+        if opts.store_gas_profiles {
+            execution_outcomes
+                .iter()
+                .flat_map(|outcome| {
+                    models::ExecutionOutcomeGasProfile::from_execution_outcome(
+                        &outcome.execution_outcome,
+                        block_timestamp,
+                    )
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+    let outcome_proof_models: Vec<models::ExecutionOutcomeProof> = if opts.store_outcome_proofs {
+        execution_outcomes
+            .iter()
+            .map(|outcome| {
+                models::ExecutionOutcomeProof::from_execution_outcome(
+                    &outcome.execution_outcome,
+                    block_timestamp,
+                )
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+
+    try_join!(
+        models::chunked_insert(db, &outcome_receipt_models),
+        models::chunked_insert(db, &outcome_proof_models),
+        models::chunked_insert(db, &outcome_log_models),
+        models::chunked_insert(db, &outcome_gas_profile_models),
+        crate::db_adapters::ft_events::store_ft_events(db, execution_outcomes, block_timestamp),
+        crate::db_adapters::nft_events::store_nft_events(db, execution_outcomes, block_timestamp),
+    )?;
 
     Ok(())
 }
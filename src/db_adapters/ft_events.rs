@@ -0,0 +1,24 @@
+use crate::models;
+
+/// Saves NEP-141 events from the logs of the successfully executed receipts
+pub(crate) async fn store_ft_events(
+    db: models::DbHandle<'_>,
+    execution_outcomes: &[near_indexer_primitives::IndexerExecutionOutcomeWithReceipt],
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let ft_events: Vec<models::FtEvent> = crate::db_adapters::collect_events(
+        execution_outcomes,
+        block_timestamp,
+        models::FtEvent::from_event_log,
+    )
+    .into_iter()
+    .map(|(event_index, mut ft_event)| {
+        ft_event.event_index = event_index;
+        ft_event
+    })
+    .collect();
+
+    models::chunked_insert(db, &ft_events).await?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use crate::models;
+
+/// Saves NEP-171 events from the logs of the successfully executed receipts
+pub(crate) async fn store_nft_events(
+    db: models::DbHandle<'_>,
+    execution_outcomes: &[near_indexer_primitives::IndexerExecutionOutcomeWithReceipt],
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let nft_events: Vec<models::NftEvent> = crate::db_adapters::collect_events(
+        execution_outcomes,
+        block_timestamp,
+        models::NftEvent::from_event_log,
+    )
+    .into_iter()
+    .map(|(event_index, mut nft_event)| {
+        nft_event.event_index = event_index;
+        nft_event
+    })
+    .collect();
+
+    models::chunked_insert(db, &nft_events).await?;
+
+    Ok(())
+}
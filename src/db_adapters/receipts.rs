@@ -13,7 +13,7 @@ use crate::models;
 
 /// Saves receipts to database
 pub(crate) async fn store_receipts(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     strict_mode: bool,
     shards: &[near_indexer_primitives::IndexerShard],
     block_header: &near_indexer_primitives::views::BlockHeaderView,
@@ -25,7 +25,7 @@ pub(crate) async fn store_receipts(
         .filter(|chunk| !chunk.receipts.is_empty())
         .map(|chunk| {
             store_chunk_receipts(
-                pool,
+                db,
                 strict_mode,
                 &chunk.receipts,
                 block_header,
@@ -38,7 +38,7 @@ pub(crate) async fn store_receipts(
 }
 
 async fn store_chunk_receipts(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     strict_mode: bool,
     receipts: &[near_indexer_primitives::views::ReceiptView],
     block_header: &near_indexer_primitives::views::BlockHeaderView,
@@ -49,7 +49,7 @@ async fn store_chunk_receipts(
         crate::ReceiptOrDataId,
         crate::ParentTransactionHashString,
     > = find_tx_hashes_for_receipts(
-        pool,
+        db,
         strict_mode,
         receipts.to_vec(),
         block_header.height,
@@ -125,10 +125,10 @@ async fn store_chunk_receipts(
     );
 
     let process_receipt_actions_future =
-        store_receipt_actions(pool, action_receipts, block_header, chunk_header);
+        store_receipt_actions(db, action_receipts, block_header, chunk_header);
 
     let process_receipt_data_future =
-        store_data_receipts(pool, data_receipts, block_header, chunk_header);
+        store_data_receipts(db, data_receipts, block_header, chunk_header);
 
     try_join!(process_receipt_actions_future, process_receipt_data_future)?;
     Ok(())
@@ -136,7 +136,7 @@ async fn store_chunk_receipts(
 
 /// Looks for already created parent transaction hash for given receipts
 async fn find_tx_hashes_for_receipts(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     strict_mode: bool,
     mut receipts: Vec<near_indexer_primitives::views::ReceiptView>,
     block_height: u64,
@@ -175,6 +175,7 @@ async fn find_tx_hashes_for_receipts(
     }));
     // releasing the lock
     drop(receipts_cache_lock);
+    crate::metrics::RECEIPTS_CACHE_HITS.inc_by(tx_hashes_for_receipts.len() as u64);
 
     // discard the Receipts already in cache from the attempts to search
     receipts.retain(|r| match r.receipt {
@@ -186,6 +187,7 @@ async fn find_tx_hashes_for_receipts(
         }
     });
 
+    crate::metrics::RECEIPTS_CACHE_MISSES.inc_by(receipts.len() as u64);
     if receipts.is_empty() {
         return Ok(tx_hashes_for_receipts);
     }
@@ -207,8 +209,9 @@ async fn find_tx_hashes_for_receipts(
         });
 
     if !data_ids.is_empty() {
+        crate::metrics::RECEIPTS_DB_FALLBACK_LOOKUPS.inc();
         let tx_hashes_for_data_receipts =
-            find_transaction_hashes_for_data_receipts(pool, &data_ids).await?;
+            find_transaction_hashes_for_data_receipts(db, &data_ids).await?;
         tx_hashes_for_receipts.extend(tx_hashes_for_data_receipts.clone());
 
         receipts.retain(|r| match r.receipt {
@@ -223,8 +226,9 @@ async fn find_tx_hashes_for_receipts(
     }
 
     if !action_receipt_ids.is_empty() {
+        crate::metrics::RECEIPTS_DB_FALLBACK_LOOKUPS.inc();
         let tx_hashes_for_receipts_via_outcomes =
-            find_transaction_hashes_for_receipts_via_outcomes(pool, &action_receipt_ids).await?;
+            find_transaction_hashes_for_receipts_via_outcomes(db, &action_receipt_ids).await?;
         tx_hashes_for_receipts.extend(tx_hashes_for_receipts_via_outcomes.clone());
 
         receipts.retain(|r| {
@@ -235,8 +239,9 @@ async fn find_tx_hashes_for_receipts(
             return Ok(tx_hashes_for_receipts);
         }
 
+        crate::metrics::RECEIPTS_DB_FALLBACK_LOOKUPS.inc();
         let tx_hashes_for_receipt_via_transactions =
-            find_transaction_hashes_for_receipt_via_transactions(pool, &action_receipt_ids).await?;
+            find_transaction_hashes_for_receipt_via_transactions(db, &action_receipt_ids).await?;
         tx_hashes_for_receipts.extend(tx_hashes_for_receipt_via_transactions.clone());
 
         receipts.retain(|r| {
@@ -262,21 +267,21 @@ async fn find_tx_hashes_for_receipts(
         let mut args = sqlx::postgres::PgArguments::default();
         args.add(BigDecimal::from(block_height));
         let query = "INSERT INTO _blocks_to_rerun VALUES ($1) ON CONFLICT DO NOTHING";
-        sqlx::query_with(query, args).execute(pool).await?;
+        db.execute(query, args).await?;
     }
 
     Ok(tx_hashes_for_receipts)
 }
 
 async fn find_transaction_hashes_for_data_receipts(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     data_ids: &[String],
 ) -> anyhow::Result<HashMap<crate::ReceiptOrDataId, crate::ParentTransactionHashString>> {
     let query = "SELECT action_receipts__outputs.output_data_id, action_receipts.originated_from_transaction_hash
                         FROM action_receipts__outputs JOIN action_receipts ON action_receipts__outputs.receipt_id = action_receipts.receipt_id
                         WHERE action_receipts__outputs.output_data_id IN ".to_owned() + &models::create_placeholder(&mut 1,data_ids.len())?;
 
-    let res = models::select_retry_or_panic(pool, &query, data_ids).await?;
+    let res = models::select_retry_or_panic(db, &query, data_ids).await?;
     Ok(res
         .iter()
         .map(|q| (q.get(0), q.get(1)))
@@ -295,14 +300,14 @@ async fn find_transaction_hashes_for_data_receipts(
 }
 
 async fn find_transaction_hashes_for_receipts_via_outcomes(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     action_receipt_ids: &[String],
 ) -> anyhow::Result<HashMap<crate::ReceiptOrDataId, crate::ParentTransactionHashString>> {
     let query = "SELECT execution_outcomes__receipts.produced_receipt_id, action_receipts.originated_from_transaction_hash
                         FROM execution_outcomes__receipts JOIN action_receipts ON execution_outcomes__receipts.executed_receipt_id = action_receipts.receipt_id
                         WHERE execution_outcomes__receipts.produced_receipt_id IN ".to_owned() + &models::create_placeholder(&mut 1,action_receipt_ids.len())?;
 
-    let res = models::select_retry_or_panic(pool, &query, action_receipt_ids).await?;
+    let res = models::select_retry_or_panic(db, &query, action_receipt_ids).await?;
     Ok(res
         .iter()
         .map(|q| (q.get(0), q.get(1)))
@@ -321,7 +326,7 @@ async fn find_transaction_hashes_for_receipts_via_outcomes(
 }
 
 async fn find_transaction_hashes_for_receipt_via_transactions(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     action_receipt_ids: &[String],
 ) -> anyhow::Result<HashMap<crate::ReceiptOrDataId, crate::ParentTransactionHashString>> {
     let query = "SELECT converted_into_receipt_id, transaction_hash
@@ -330,7 +335,7 @@ async fn find_transaction_hashes_for_receipt_via_transactions(
         .to_owned()
         + &models::create_placeholder(&mut 1, action_receipt_ids.len())?;
 
-    let res = models::select_retry_or_panic(pool, &query, action_receipt_ids).await?;
+    let res = models::select_retry_or_panic(db, &query, action_receipt_ids).await?;
     Ok(res
         .iter()
         .map(|q| (q.get(0), q.get(1)))
@@ -349,7 +354,7 @@ async fn find_transaction_hashes_for_receipt_via_transactions(
 }
 
 async fn store_receipt_actions(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     receipts: Vec<(usize, &String, &near_indexer_primitives::views::ReceiptView)>,
     block_header: &near_indexer_primitives::views::BlockHeaderView,
     chunk_header: &near_indexer_primitives::views::ChunkHeaderView,
@@ -431,23 +436,23 @@ async fn store_receipt_actions(
         .collect();
 
     // Next 2 tables depend on action_receipts, so we have to wait for it at first
-    models::chunked_insert(pool, &receipt_actions).await?;
+    models::chunked_insert(db, &receipt_actions).await?;
     try_join!(
-        models::chunked_insert(pool, &receipt_action_actions),
-        models::chunked_insert(pool, &receipt_action_output_data),
+        models::chunked_insert(db, &receipt_action_actions),
+        models::chunked_insert(db, &receipt_action_output_data),
     )?;
 
     Ok(())
 }
 
 async fn store_data_receipts(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     receipts: Vec<(usize, &String, &near_indexer_primitives::views::ReceiptView)>,
     block_header: &near_indexer_primitives::views::BlockHeaderView,
     chunk_header: &near_indexer_primitives::views::ChunkHeaderView,
 ) -> anyhow::Result<()> {
     models::chunked_insert(
-        pool,
+        db,
         &receipts
             .iter()
             .filter_map(|(index, tx, receipt)| {
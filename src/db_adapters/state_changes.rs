@@ -0,0 +1,50 @@
+use futures::future::try_join_all;
+
+use crate::models;
+
+pub(crate) async fn store_state_changes(
+    db: models::DbHandle<'_>,
+    shards: &[near_indexer_primitives::IndexerShard],
+    block_hash: &near_indexer_primitives::CryptoHash,
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let futures = shards.iter().map(|shard| {
+        store_state_changes_for_chunk(
+            db,
+            &shard.state_changes,
+            block_hash,
+            block_timestamp,
+            shard.shard_id,
+        )
+    });
+
+    try_join_all(futures).await.map(|_| ())
+}
+
+async fn store_state_changes_for_chunk(
+    db: models::DbHandle<'_>,
+    state_changes: &near_indexer_primitives::views::StateChangesView,
+    block_hash: &near_indexer_primitives::CryptoHash,
+    block_timestamp: u64,
+    shard_id: near_indexer_primitives::types::ShardId,
+) -> anyhow::Result<()> {
+    models::chunked_insert(
+        db,
+        &state_changes
+            .iter()
+            .enumerate()
+            .map(|(index_in_chunk, state_change)| {
+                models::StateChange::from_state_change_with_cause(
+                    state_change,
+                    block_hash,
+                    block_timestamp,
+                    shard_id as i32,
+                    index_in_chunk as i32,
+                )
+            })
+            .collect::<Vec<models::StateChange>>(),
+    )
+    .await?;
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use futures::future::try_join_all;
 use crate::models;
 
 pub(crate) async fn store_transactions(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     shards: &[near_indexer_primitives::IndexerShard],
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
@@ -16,7 +16,7 @@ pub(crate) async fn store_transactions(
         .filter(|chunk| !chunk.transactions.is_empty())
         .map(|chunk| {
             store_chunk_transactions(
-                pool,
+                db,
                 &chunk.transactions,
                 block_hash,
                 block_timestamp,
@@ -30,7 +30,7 @@ pub(crate) async fn store_transactions(
 }
 
 async fn store_chunk_transactions(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     transactions: &[near_indexer_primitives::IndexerTransactionWithOutcome],
     block_hash: &near_indexer_primitives::CryptoHash,
     block_timestamp: u64,
@@ -73,7 +73,25 @@ async fn store_chunk_transactions(
         .collect::<Vec<models::Transaction>>();
     drop(receipts_cache_lock);
 
-    models::chunked_insert(pool, &transaction_models).await?;
+    let transaction_action_models: Vec<models::TransactionAction> = transactions
+        .iter()
+        .flat_map(|transaction| {
+            transaction.transaction.actions.iter().enumerate().map(
+                |(index_in_transaction, action_view)| {
+                    models::TransactionAction::from_action_view(
+                        &transaction.transaction,
+                        action_view,
+                        index_in_transaction as i32,
+                        block_timestamp,
+                    )
+                },
+            )
+        })
+        .collect();
+
+    models::chunked_insert(db, &transaction_models).await?;
+    // FK to transaction_hash
+    models::chunked_insert(db, &transaction_action_models).await?;
 
     Ok(())
 }
@@ -0,0 +1,285 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+use bigdecimal::BigDecimal;
+use num_traits::ToPrimitive;
+use serde::Deserialize;
+use sqlx::Row;
+
+use crate::configs::Opts;
+use crate::models;
+
+// Genesis records don't belong to any chunk, so they don't clash with the changes of the genesis block
+const GENESIS_CHUNK_INDEX: i32 = -1;
+const GENESIS_RECORDS_BATCH_SIZE: usize = 10_000;
+
+#[derive(Deserialize)]
+struct GenesisHeight {
+    genesis_height: u64,
+}
+
+/// Stores the accounts, the access keys and the contract codes from genesis as the changes
+/// with INITIAL_STATE cause. They are attached to the genesis block, so it has to be indexed before.
+/// The records always go to `state_changes` table, it's the only place for the contract codes
+pub(crate) async fn import_genesis(
+    opts: &Opts,
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    genesis_file: &Path,
+    genesis_height: Option<u64>,
+) -> anyhow::Result<()> {
+    let genesis_height = match genesis_height {
+        Some(genesis_height) => genesis_height,
+        None => {
+            let genesis_file = genesis_file.to_path_buf();
+            tokio::task::spawn_blocking(move || read_genesis_height(&genesis_file)).await??
+        }
+    };
+
+    let db = models::DbHandle {
+        connection: models::DbConnection::Pool(pool),
+        write_mode: opts.write_mode,
+    };
+
+    let query = "SELECT block_hash, block_timestamp FROM blocks WHERE block_height = $1::numeric";
+    let res = models::select_retry_or_panic(db, query, &[genesis_height.to_string()]).await?;
+    let row = res.first().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Genesis block {} is not indexed yet, please index it before the import",
+            genesis_height
+        )
+    })?;
+    let block_hash = near_indexer_primitives::CryptoHash::from_str(&row.get::<String, _>(0))
+        .map_err(|err| anyhow::anyhow!("Failed to convert String to CryptoHash: {}", err))?;
+    let block_timestamp = row
+        .get::<BigDecimal, _>(1)
+        .to_u64()
+        .ok_or_else(|| anyhow::anyhow!("`block_timestamp` expected to be u64"))?;
+
+    // The file is parsed on the blocking thread, the batches are stored while the rest is being read
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(1);
+    let reader = {
+        let genesis_file = genesis_file.to_path_buf();
+        tokio::task::spawn_blocking(move || stream_genesis_records(genesis_file, sender))
+    };
+
+    let mut records_count: usize = 0;
+    while let Some(records) = receiver.recv().await {
+        // The index of the record in genesis goes to `index_in_chunk`, so the import is idempotent
+        let mut state_changes = Vec::with_capacity(records.len());
+        for record in records {
+            if let Some(value) = state_change_value_from_record(record)? {
+                state_changes.push((
+                    records_count as i32,
+                    near_indexer_primitives::views::StateChangeWithCauseView {
+                        cause: near_indexer_primitives::views::StateChangeCauseView::InitialState,
+                        value,
+                    },
+                ));
+            }
+            records_count += 1;
+        }
+
+        store_genesis_state_changes(db, &state_changes, &block_hash, block_timestamp).await?;
+        eprintln!("{} genesis records are processed", records_count);
+    }
+    reader.await??;
+
+    Ok(())
+}
+
+/// Reads `genesis_height` from genesis.json, the records are skipped without being collected.
+/// The file with the array of genesis records doesn't have it, `--genesis-height` is required there
+fn read_genesis_height(genesis_file: &Path) -> anyhow::Result<u64> {
+    let file = std::fs::File::open(genesis_file)
+        .with_context(|| format!("Failed to read {}", genesis_file.display()))?;
+    let genesis: GenesisHeight = serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| {
+            format!(
+                "Failed to read genesis_height from {}, pass --genesis-height for the file with genesis records",
+                genesis_file.display()
+            )
+        })?;
+    Ok(genesis.genesis_height)
+}
+
+/// Reads either genesis.json or the file with the array of genesis records
+/// and sends the records by batches of GENESIS_RECORDS_BATCH_SIZE
+fn stream_genesis_records(
+    genesis_file: PathBuf,
+    sender: tokio::sync::mpsc::Sender<Vec<serde_json::Value>>,
+) -> anyhow::Result<()> {
+    let file = std::fs::File::open(&genesis_file)
+        .with_context(|| format!("Failed to read {}", genesis_file.display()))?;
+    let mut deserializer = serde_json::Deserializer::from_reader(std::io::BufReader::new(file));
+    serde::Deserializer::deserialize_any(&mut deserializer, GenesisRecordsVisitor { sender })
+        .and_then(|_| deserializer.end())
+        .with_context(|| format!("Failed to parse {}", genesis_file.display()))
+}
+
+/// Visits the top level array of records or the `records` field of genesis.json,
+/// the records are never collected all together
+#[derive(Clone)]
+struct GenesisRecordsVisitor {
+    sender: tokio::sync::mpsc::Sender<Vec<serde_json::Value>>,
+}
+
+impl GenesisRecordsVisitor {
+    fn send<E: serde::de::Error>(&self, records: Vec<serde_json::Value>) -> Result<(), E> {
+        self.sender
+            .blocking_send(records)
+            .map_err(|_| E::custom("genesis records are not awaited anymore"))
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for GenesisRecordsVisitor {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for GenesisRecordsVisitor {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("genesis or the array of genesis records")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        let mut records = Vec::with_capacity(GENESIS_RECORDS_BATCH_SIZE);
+        while let Some(record) = seq.next_element()? {
+            records.push(record);
+            if records.len() == GENESIS_RECORDS_BATCH_SIZE {
+                self.send(std::mem::replace(
+                    &mut records,
+                    Vec::with_capacity(GENESIS_RECORDS_BATCH_SIZE),
+                ))?;
+            }
+        }
+        if !records.is_empty() {
+            self.send(records)?;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut has_records = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "records" {
+                map.next_value_seed(self.clone())?;
+                has_records = true;
+            } else {
+                map.next_value::<serde::de::IgnoredAny>()?;
+            }
+        }
+        if !has_records {
+            return Err(serde::de::Error::missing_field("records"));
+        }
+        Ok(())
+    }
+}
+
+/// Converts the genesis record to the view of the same change we get from the stream.
+/// The records except accounts, access keys and contract codes are skipped
+fn state_change_value_from_record(
+    record: serde_json::Value,
+) -> anyhow::Result<Option<near_indexer_primitives::views::StateChangeValueView>> {
+    let (record_type, mut record_value) = match record {
+        serde_json::Value::Object(record) => match record.into_iter().next() {
+            Some(record) => record,
+            None => return Ok(None),
+        },
+        _ => return Err(anyhow::anyhow!("Genesis record expected to be an object")),
+    };
+
+    let change = match record_type.as_str() {
+        "Account" => {
+            let account = record_value
+                .get_mut("account")
+                .map(serde_json::Value::take)
+                .unwrap_or_default();
+            let mut change = account;
+            change["account_id"] = record_value["account_id"].take();
+            serde_json::json!({"type": "account_update", "change": change})
+        }
+        "AccessKey" => serde_json::json!({"type": "access_key_update", "change": record_value}),
+        "Contract" => serde_json::json!({
+            "type": "contract_code_update",
+            "change": {
+                "account_id": record_value["account_id"].take(),
+                "code_base64": record_value["code"].take(),
+            },
+        }),
+        _ => return Ok(None),
+    };
+
+    serde_json::from_value(change)
+        .map(Some)
+        .with_context(|| format!("Failed to parse genesis record {}", record_type))
+}
+
+async fn store_genesis_state_changes(
+    db: models::DbHandle<'_>,
+    state_changes: &[(
+        i32,
+        near_indexer_primitives::views::StateChangeWithCauseView,
+    )],
+    block_hash: &near_indexer_primitives::CryptoHash,
+    block_timestamp: u64,
+) -> anyhow::Result<()> {
+    let mut account_changes: Vec<models::AccountChange> = vec![];
+    let mut accounts: Vec<models::Account> = vec![];
+    let mut access_keys: Vec<models::AccessKey> = vec![];
+    let mut all_state_changes: Vec<models::StateChange> = vec![];
+
+    for (index, state_change) in state_changes {
+        match &state_change.value {
+            near_indexer_primitives::views::StateChangeValueView::AccountUpdate {
+                account_id,
+                ..
+            } => {
+                account_changes.extend(models::AccountChange::from_state_change_with_cause(
+                    state_change,
+                    block_hash,
+                    block_timestamp,
+                    GENESIS_CHUNK_INDEX,
+                    *index,
+                ));
+                accounts.push(models::Account::new_created(
+                    account_id.to_string(),
+                    None,
+                    block_timestamp,
+                ));
+            }
+            near_indexer_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                account_id,
+                public_key,
+                access_key,
+            } => access_keys.push(models::AccessKey::from_access_key_view(
+                public_key.to_string(),
+                account_id.to_string(),
+                access_key,
+                None,
+                block_timestamp,
+            )),
+            _ => {}
+        }
+        all_state_changes.push(models::StateChange::from_state_change_with_cause(
+            state_change,
+            block_hash,
+            block_timestamp,
+            GENESIS_CHUNK_INDEX,
+            *index,
+        ));
+    }
+
+    models::chunked_insert(db, &account_changes).await?;
+    models::chunked_insert(db, &accounts).await?;
+    models::chunked_insert(db, &access_keys).await?;
+    models::chunked_insert(db, &all_state_changes).await?;
+
+    Ok(())
+}
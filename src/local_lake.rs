@@ -0,0 +1,98 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use near_indexer_primitives::StreamerMessage;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Reads the blocks from the local folder laid out like the lake bucket:
+/// `<block_height padded with zeros>/block.json` and `shard_N.json` for each chunk of the block.
+/// The stream stops both after the last block in the folder and after a failure,
+/// so the returned reader tells whether the folder was read to the end
+pub(crate) fn streamer(
+    path: PathBuf,
+    start_block_height: u64,
+) -> (
+    JoinHandle<anyhow::Result<()>>,
+    mpsc::Receiver<StreamerMessage>,
+) {
+    let (sender, receiver) = mpsc::channel(100);
+    let reader = tokio::spawn(async move {
+        start(&path, start_block_height, sender)
+            .await
+            .with_context(|| format!("Failed to read the blocks from {}", path.display()))
+    });
+    (reader, receiver)
+}
+
+/// Returns the error of the local folder reader if there is one.
+/// Should be called when the stream is closed or dropped, otherwise the reader could wait for the receiver forever
+pub(crate) async fn wait_reader(
+    reader: Option<JoinHandle<anyhow::Result<()>>>,
+) -> anyhow::Result<()> {
+    match reader {
+        Some(reader) => reader.await?,
+        None => Ok(()),
+    }
+}
+
+async fn start(
+    path: &Path,
+    start_block_height: u64,
+    sender: mpsc::Sender<StreamerMessage>,
+) -> anyhow::Result<()> {
+    for block_path in list_block_paths(path, start_block_height).await? {
+        let streamer_message = read_block(&block_path).await?;
+        if sender.send(streamer_message).await.is_err() {
+            // The receiver is dropped, nobody waits for the next blocks
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the folders of the blocks starting from the given height, in ascending order
+async fn list_block_paths(path: &Path, start_block_height: u64) -> anyhow::Result<Vec<PathBuf>> {
+    let mut entries = tokio::fs::read_dir(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let mut block_paths = vec![];
+    while let Some(entry) = entries.next_entry().await? {
+        let block_height = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u64>().ok());
+        match block_height {
+            Some(block_height) if block_height >= start_block_height => {
+                block_paths.push((block_height, entry.path()))
+            }
+            _ => {}
+        }
+    }
+
+    block_paths.sort_unstable_by_key(|(block_height, _)| *block_height);
+    Ok(block_paths
+        .into_iter()
+        .map(|(_, block_path)| block_path)
+        .collect())
+}
+
+async fn read_block(block_path: &Path) -> anyhow::Result<StreamerMessage> {
+    let block: near_indexer_primitives::views::BlockView =
+        read_json(&block_path.join("block.json")).await?;
+
+    let mut shards = Vec::with_capacity(block.chunks.len());
+    for shard_id in 0..block.chunks.len() {
+        shards.push(read_json(&block_path.join(format!("shard_{}.json", shard_id))).await?);
+    }
+
+    Ok(StreamerMessage { block, shards })
+}
+
+async fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> anyhow::Result<T> {
+    let content = tokio::fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_slice(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
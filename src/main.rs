@@ -3,18 +3,26 @@ use clap::Parser;
 use dotenv::dotenv;
 use futures::{try_join, StreamExt};
 use std::env;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 use tracing_subscriber::EnvFilter;
 
-use crate::configs::Opts;
+use crate::configs::{Opts, SubCommand};
 
+mod backfill;
 mod configs;
 mod db_adapters;
+mod genesis;
+mod local_lake;
+mod metrics;
 mod models;
+mod rerun;
+mod rollback;
 
 // Categories for logging
 // TODO naming
 pub(crate) const INDEXER: &str = "indexer";
+// The key of the indexer in `_checkpoints` table
+pub(crate) const INDEXER_ID: &str = "indexer_base";
 
 const INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
 const MAX_DELAY_TIME: std::time::Duration = std::time::Duration::from_secs(120);
@@ -33,22 +41,55 @@ pub type ParentTransactionHashString = String;
 pub type ReceiptsCache =
     std::sync::Arc<Mutex<SizedCache<ReceiptOrDataId, ParentTransactionHashString>>>;
 
+/// Receipts look for their parent Transactions in ReceiptsCache and in the DB, which are filled
+/// while handling the previous blocks.
+/// When the blocks are handled concurrently, the block stores its Receipts only after
+/// the previous block finishes with its Receipts and ExecutionOutcomes
+pub(crate) struct BlockOrdering {
+    previous_block_done: Option<oneshot::Receiver<()>>,
+    current_block_done: Option<oneshot::Sender<()>>,
+}
+
+impl BlockOrdering {
+    /// For the blocks which are handled one by one
+    pub(crate) fn sequential() -> Self {
+        Self {
+            previous_block_done: None,
+            current_block_done: None,
+        }
+    }
+
+    /// Creates the ordering for the next block in the chain
+    fn chained(previous_block_done: &mut Option<oneshot::Receiver<()>>) -> Self {
+        let (sender, receiver) = oneshot::channel();
+        Self {
+            previous_block_done: previous_block_done.replace(receiver),
+            current_block_done: Some(sender),
+        }
+    }
+
+    async fn wait_for_previous_block(&mut self) {
+        if let Some(previous_block_done) = self.previous_block_done.take() {
+            // The error means the previous block failed, the stream stops on its error anyway
+            previous_block_done.await.ok();
+        }
+    }
+
+    fn finish_current_block(&mut self) {
+        if let Some(current_block_done) = self.current_block_done.take() {
+            current_block_done.send(()).ok();
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
 
     let opts: Opts = Opts::parse();
     let pool = sqlx::PgPool::connect(&env::var("DATABASE_URL")?).await?;
-    let config = near_lake_framework::LakeConfig {
-        s3_config: None,
-        s3_bucket_name: opts.s3_bucket_name.clone(),
-        s3_region_name: opts.s3_region_name.clone(),
-        start_block_height: opts.start_block_height.unwrap(),
-    };
     init_tracing();
 
-    let stream = near_lake_framework::streamer(config);
-
     // We want to prevent unnecessary SELECT queries to the database to find
     // the Transaction hash for the Receipt.
     // Later we need to find the Receipt which is a parent to underlying Receipts.
@@ -57,25 +98,84 @@ async fn main() -> anyhow::Result<()> {
     let receipts_cache: ReceiptsCache =
         std::sync::Arc::new(Mutex::new(SizedCache::with_size(100_000)));
 
+    if let Some(metrics_port) = opts.metrics_port {
+        metrics::init_server(metrics_port);
+    }
+
+    match &opts.command {
+        Some(SubCommand::Rerun) => rerun::rerun_blocks(&opts, &pool, receipts_cache).await,
+        Some(SubCommand::Rollback { to_height }) => rollback::rollback(&pool, *to_height).await,
+        Some(SubCommand::ImportGenesis {
+            genesis_file,
+            genesis_height,
+        }) => genesis::import_genesis(&opts, &pool, genesis_file, *genesis_height).await,
+        Some(SubCommand::BackfillBlocks {
+            from_height,
+            to_height,
+        }) => backfill::backfill_blocks(&opts, &pool, *from_height, *to_height).await,
+        None => index_blocks(&opts, &pool, receipts_cache).await,
+    }
+}
+
+async fn index_blocks(
+    opts: &Opts,
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    receipts_cache: ReceiptsCache,
+) -> anyhow::Result<()> {
+    let start_block_height = match opts.start_block_height {
+        Some(start_block_height) => start_block_height,
+        None => db_adapters::checkpoints::get_last_processed_block_height(models::DbHandle {
+            connection: models::DbConnection::Pool(pool),
+            write_mode: opts.write_mode,
+        })
+        .await?
+        .map(|last_processed_block_height| last_processed_block_height + 1)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "There is no checkpoint to resume from, please provide --start-block-height"
+            )
+        })?,
+    };
+    eprintln!("Starting the stream from block {}", start_block_height);
+    let (reader, stream) = opts.streamer(start_block_height).await?;
+
+    if let Some(blocks_per_transaction) = opts.blocks_per_transaction {
+        index_blocks_in_transactions(stream, pool, receipts_cache, opts, blocks_per_transaction)
+            .await?;
+        return local_lake::wait_reader(reader).await;
+    }
+
+    let db = models::DbHandle {
+        connection: models::DbConnection::Pool(pool),
+        write_mode: opts.write_mode,
+    };
+    let mut previous_block_done = None;
+    // `buffered` keeps the order of the blocks, so the checkpoint below always moves forward
     let mut handlers = tokio_stream::wrappers::ReceiverStream::new(stream)
         .map(|streamer_message| {
             handle_streamer_message(
                 streamer_message,
-                &pool,
+                db,
                 receipts_cache.clone(),
+                opts,
                 !opts.non_strict_mode,
+                BlockOrdering::chained(&mut previous_block_done),
             )
         })
-        .buffer_unordered(1usize);
+        .buffered(opts.concurrency.get());
 
     // let mut time_now = std::time::Instant::now();
     while let Some(handle_message) = handlers.next().await {
         match handle_message {
-            Ok(_block_height) => {
+            Ok(block_height) => {
+                // All the tables for this block are stored, it's safe to resume from the next one
+                db_adapters::checkpoints::update_last_processed_block_height(db, block_height)
+                    .await?;
+                metrics::LAST_INDEXED_BLOCK_HEIGHT.set(block_height as i64);
                 // let elapsed = time_now.elapsed();
                 // println!(
                 //     "Elapsed time spent on block {}: {:.3?}",
-                //     _block_height, elapsed
+                //     block_height, elapsed
                 // );
                 // time_now = std::time::Instant::now();
             }
@@ -85,14 +185,60 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // The stream is closed, it could be the failure of the local folder reader
+    local_lake::wait_reader(reader).await
+}
+
+/// Writes all the rows of `blocks_per_transaction` consecutive blocks through one database transaction.
+/// The checkpoint is updated in the same transaction, so after a failure we resume exactly from
+/// the first block which was not committed
+async fn index_blocks_in_transactions(
+    mut stream: tokio::sync::mpsc::Receiver<near_indexer_primitives::StreamerMessage>,
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    receipts_cache: ReceiptsCache,
+    opts: &Opts,
+    blocks_per_transaction: u64,
+) -> anyhow::Result<()> {
+    let transaction = Mutex::new(pool.begin().await?);
+    let mut blocks_in_transaction = 0u64;
+
+    while let Some(streamer_message) = stream.recv().await {
+        let db = models::DbHandle {
+            connection: models::DbConnection::Transaction(&transaction),
+            write_mode: opts.write_mode,
+        };
+        let block_height = handle_streamer_message(
+            streamer_message,
+            db,
+            receipts_cache.clone(),
+            opts,
+            !opts.non_strict_mode,
+            BlockOrdering::sequential(),
+        )
+        .await?;
+        db_adapters::checkpoints::update_last_processed_block_height(db, block_height).await?;
+
+        blocks_in_transaction += 1;
+        if blocks_in_transaction >= blocks_per_transaction {
+            let finished_transaction =
+                std::mem::replace(&mut *transaction.lock().await, pool.begin().await?);
+            finished_transaction.commit().await?;
+            metrics::LAST_INDEXED_BLOCK_HEIGHT.set(block_height as i64);
+            blocks_in_transaction = 0;
+        }
+    }
+
+    transaction.into_inner().commit().await?;
     Ok(())
 }
 
-async fn handle_streamer_message(
+pub(crate) async fn handle_streamer_message(
     streamer_message: near_indexer_primitives::StreamerMessage,
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: models::DbHandle<'_>,
     receipts_cache: ReceiptsCache,
+    opts: &Opts,
     strict_mode: bool,
+    mut block_ordering: BlockOrdering,
 ) -> anyhow::Result<u64> {
     if streamer_message.block.header.height % 100 == 0 {
         eprintln!(
@@ -102,17 +248,13 @@ async fn handle_streamer_message(
         );
     }
 
-    let blocks_future = db_adapters::blocks::store_block(pool, &streamer_message.block);
+    let blocks_future = db_adapters::blocks::store_block(db, &streamer_message.block);
 
-    let chunks_future = db_adapters::chunks::store_chunks(
-        pool,
-        &streamer_message.shards,
-        &streamer_message.block.header.hash,
-        streamer_message.block.header.timestamp,
-    );
+    let chunks_future =
+        db_adapters::chunks::store_chunks(db, &streamer_message.block, &streamer_message.shards);
 
     let transactions_future = db_adapters::transactions::store_transactions(
-        pool,
+        db,
         &streamer_message.shards,
         &streamer_message.block.header.hash,
         streamer_message.block.header.timestamp,
@@ -120,7 +262,7 @@ async fn handle_streamer_message(
     );
 
     let receipts_future = db_adapters::receipts::store_receipts(
-        pool,
+        db,
         strict_mode,
         &streamer_message.shards,
         &streamer_message.block.header,
@@ -128,15 +270,56 @@ async fn handle_streamer_message(
     );
 
     let execution_outcomes_future = db_adapters::execution_outcomes::store_execution_outcomes(
-        pool,
+        db,
         &streamer_message.shards,
         &streamer_message.block.header.hash,
         streamer_message.block.header.timestamp,
         receipts_cache.clone(),
+        opts,
     );
 
     let account_changes_future = db_adapters::account_changes::store_account_changes(
-        pool,
+        db,
+        &streamer_message.shards,
+        &streamer_message.block.header.hash,
+        streamer_message.block.header.timestamp,
+    );
+
+    let access_keys_future = db_adapters::access_keys::store_access_keys(
+        db,
+        &streamer_message.shards,
+        streamer_message.block.header.timestamp,
+    );
+
+    let accounts_future = db_adapters::accounts::store_accounts(
+        db,
+        &streamer_message.shards,
+        streamer_message.block.header.timestamp,
+    );
+
+    let contract_deployments_future = db_adapters::contract_deployments::store_contract_deployments(
+        db,
+        &streamer_message.shards,
+        streamer_message.block.header.timestamp,
+    );
+
+    let contract_state_changes_future =
+        db_adapters::contract_state_changes::store_contract_state_changes(
+            db,
+            &streamer_message.shards,
+            &streamer_message.block.header.hash,
+            streamer_message.block.header.timestamp,
+            &opts.contract_state_accounts,
+        );
+
+    let epochs_future = db_adapters::epochs::store_epoch_and_validators(
+        db,
+        &streamer_message.block,
+        &streamer_message.shards,
+    );
+
+    let state_changes_future = db_adapters::state_changes::store_state_changes(
+        db,
         &streamer_message.shards,
         &streamer_message.block.header.hash,
         streamer_message.block.header.timestamp,
@@ -147,14 +330,30 @@ async fn handle_streamer_message(
     chunks_future.await?;
     // we have FK both to blocks and chunks
     transactions_future.await?;
+    // receipts could be produced by the previous block, so their parents have to be already known
+    block_ordering.wait_for_previous_block().await;
     // this guy can contain local receipts, so we have to do that after transactions_future finished the work
     receipts_future.await?;
     try_join!(
         // this guy depends on transactions and receipts with its FKs
         account_changes_future,
+        // this guy depends on receipts with its FKs and on the keys created in the previous blocks
+        access_keys_future,
+        // this guy depends on receipts with its FKs and on the accounts created in the previous blocks
+        accounts_future,
+        // this guy depends on receipts with its FKs
+        contract_deployments_future,
+        // this guy depends on receipts with its FKs
+        contract_state_changes_future,
+        // this guy depends only on blocks with its FKs
+        state_changes_future,
+        // this guy relies on the order of the blocks to count each of them only once
+        epochs_future,
         // this guy thinks that receipts_future finished, and clears the cache
         execution_outcomes_future
     )?;
+    block_ordering.finish_current_block();
+    metrics::observe_block_lag(streamer_message.block.header.timestamp);
     Ok(streamer_message.block.header.height)
 }
 
@@ -0,0 +1,90 @@
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server, StatusCode};
+use lazy_static::lazy_static;
+use prometheus::{
+    register_gauge, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, Encoder, Gauge, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+};
+
+lazy_static! {
+    pub(crate) static ref LAST_INDEXED_BLOCK_HEIGHT: IntGauge = register_int_gauge!(
+        "indexer_base_last_indexed_block_height",
+        "Height of the last block which is fully stored and checkpointed"
+    )
+    .unwrap();
+    pub(crate) static ref BLOCK_LAG_SECONDS: Gauge = register_gauge!(
+        "indexer_base_block_lag_seconds",
+        "Difference between the current time and the timestamp of the last handled block"
+    )
+    .unwrap();
+    pub(crate) static ref ROWS_WRITTEN: IntCounterVec = register_int_counter_vec!(
+        "indexer_base_rows_written_total",
+        "Number of rows sent to the database, per table",
+        &["table"]
+    )
+    .unwrap();
+    pub(crate) static ref DB_RETRIES: IntCounterVec = register_int_counter_vec!(
+        "indexer_base_db_retries_total",
+        "Number of failed database queries which were retried, per table (`select` for the lookups)",
+        &["table"]
+    )
+    .unwrap();
+    pub(crate) static ref DB_QUERY_DURATION_SECONDS: HistogramVec = register_histogram_vec!(
+        "indexer_base_db_query_duration_seconds",
+        "Duration of a single database query attempt, per table (`select` for the lookups)",
+        &["table"]
+    )
+    .unwrap();
+    pub(crate) static ref RECEIPTS_CACHE_HITS: IntCounter = register_int_counter!(
+        "indexer_base_receipts_cache_hits_total",
+        "Number of receipts which parent transaction hash was found in ReceiptsCache"
+    )
+    .unwrap();
+    pub(crate) static ref RECEIPTS_CACHE_MISSES: IntCounter = register_int_counter!(
+        "indexer_base_receipts_cache_misses_total",
+        "Number of receipts which parent transaction hash was not found in ReceiptsCache"
+    )
+    .unwrap();
+    pub(crate) static ref RECEIPTS_DB_FALLBACK_LOOKUPS: IntCounter = register_int_counter!(
+        "indexer_base_receipts_db_fallback_lookups_total",
+        "Number of database queries looking for the parent transaction hash of the receipts missing in ReceiptsCache"
+    )
+    .unwrap();
+}
+
+pub(crate) fn observe_block_lag(block_timestamp: u64) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    BLOCK_LAG_SECONDS.set(now.saturating_sub(block_timestamp) as f64 / 1_000_000_000f64);
+}
+
+async fn serve(request: Request<Body>) -> anyhow::Result<Response<Body>> {
+    if request.uri().path() != "/metrics" {
+        return Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty())?);
+    }
+
+    let encoder = prometheus::TextEncoder::new();
+    let mut buffer = vec![];
+    encoder.encode(&prometheus::gather(), &mut buffer)?;
+
+    Ok(Response::builder()
+        .header(hyper::header::CONTENT_TYPE, encoder.format_type())
+        .body(Body::from(buffer))?)
+}
+
+/// Spawns HTTP server which exposes the metrics in Prometheus format at `/metrics`
+pub(crate) fn init_server(port: u16) {
+    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+    let make_service = make_service_fn(|_| async { Ok::<_, hyper::Error>(service_fn(serve)) });
+
+    eprintln!("Serving metrics at {}/metrics", addr);
+    tokio::spawn(async move {
+        if let Err(err) = Server::bind(&addr).serve(make_service).await {
+            eprintln!("Metrics server failed: {}", err);
+        }
+    });
+}
@@ -0,0 +1,74 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::{FieldCount, PrintEnum};
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct AccessKey {
+    pub public_key: String,
+    pub account_id: String,
+    pub created_by_receipt_id: Option<String>,
+    pub deleted_by_receipt_id: Option<String>,
+    pub created_by_block_timestamp: BigDecimal,
+    pub index_in_block: i32,
+    pub deleted_by_block_timestamp: Option<BigDecimal>,
+    pub permission_kind: String,
+    pub permission: serde_json::Value,
+}
+
+impl AccessKey {
+    /// The receipt is None for the keys from genesis
+    pub fn from_access_key_view(
+        public_key: String,
+        account_id: String,
+        access_key: &near_indexer_primitives::views::AccessKeyView,
+        created_by_receipt_id: Option<&near_indexer_primitives::CryptoHash>,
+        created_by_block_timestamp: u64,
+    ) -> Self {
+        Self {
+            public_key,
+            account_id,
+            created_by_receipt_id: created_by_receipt_id.map(|receipt_id| receipt_id.to_string()),
+            deleted_by_receipt_id: None,
+            created_by_block_timestamp: created_by_block_timestamp.into(),
+            // we fill it later because the key could be added several times in the block
+            index_in_block: 0,
+            deleted_by_block_timestamp: None,
+            permission_kind: access_key.permission.print().to_string(),
+            permission: serde_json::to_value(
+                crate::models::serializers::AccessKeyPermissionView::from(
+                    access_key.permission.clone(),
+                ),
+            )
+            .expect("AccessKeyPermissionView expected to be serializable"),
+        }
+    }
+}
+
+impl crate::models::SqlMethods for AccessKey {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.public_key);
+        args.add(&self.account_id);
+        args.add(&self.created_by_receipt_id);
+        args.add(&self.deleted_by_receipt_id);
+        args.add(&self.created_by_block_timestamp);
+        args.add(&self.index_in_block);
+        args.add(&self.deleted_by_block_timestamp);
+        args.add(&self.permission_kind);
+        args.add(&self.permission);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO access_keys VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, AccessKey::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM access_keys WHERE created_by_block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "access_keys".to_string()
+    }
+}
@@ -84,7 +84,7 @@ impl AccountChange {
 }
 
 impl crate::models::SqlMethods for AccountChange {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.account_id);
         args.add(&self.block_timestamp);
         args.add(&self.block_hash);
@@ -0,0 +1,61 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::FieldCount;
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct Account {
+    pub account_id: String,
+    pub created_by_receipt_id: Option<String>,
+    pub deleted_by_receipt_id: Option<String>,
+    pub created_by_block_timestamp: BigDecimal,
+    pub index_in_block: i32,
+    pub deleted_by_block_timestamp: Option<BigDecimal>,
+    pub beneficiary_account_id: Option<String>,
+}
+
+impl Account {
+    /// The receipt is None for the accounts from genesis
+    pub fn new_created(
+        account_id: String,
+        created_by_receipt_id: Option<&near_indexer_primitives::CryptoHash>,
+        created_by_block_timestamp: u64,
+    ) -> Self {
+        Self {
+            account_id,
+            created_by_receipt_id: created_by_receipt_id.map(|receipt_id| receipt_id.to_string()),
+            deleted_by_receipt_id: None,
+            created_by_block_timestamp: created_by_block_timestamp.into(),
+            // we fill it later because the account could be created several times in the block
+            index_in_block: 0,
+            deleted_by_block_timestamp: None,
+            beneficiary_account_id: None,
+        }
+    }
+}
+
+impl crate::models::SqlMethods for Account {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.account_id);
+        args.add(&self.created_by_receipt_id);
+        args.add(&self.deleted_by_receipt_id);
+        args.add(&self.created_by_block_timestamp);
+        args.add(&self.index_in_block);
+        args.add(&self.deleted_by_block_timestamp);
+        args.add(&self.beneficiary_account_id);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO accounts VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, Account::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM accounts WHERE created_by_block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "accounts".to_string()
+    }
+}
@@ -14,10 +14,30 @@ pub struct Block {
     pub total_supply: BigDecimal,
     pub gas_price: BigDecimal,
     pub author_account_id: String,
+    pub epoch_id: Option<String>,
+    pub next_epoch_id: Option<String>,
+    pub chunks_included: Option<BigDecimal>,
+    pub validator_reward: Option<BigDecimal>,
+    pub latest_protocol_version: Option<i32>,
+    pub block_ordinal: Option<BigDecimal>,
+    pub random_value: Option<String>,
+    pub approvals_count: Option<i32>,
+    pub gas_used: Option<BigDecimal>,
+    pub gas_limit: Option<BigDecimal>,
 }
 
 impl Block {
     pub fn from_block_view(block_view: &near_indexer_primitives::views::BlockView) -> Self {
+        // The headers of the missing chunks are taken from the previous blocks, they are not counted
+        let new_chunks = block_view
+            .chunks
+            .iter()
+            .filter(|chunk| chunk.height_included == block_view.header.height);
+        let (gas_used, gas_limit) = new_chunks
+            .fold((0u64, 0u64), |(gas_used, gas_limit), chunk| {
+                (gas_used + chunk.gas_used, gas_limit + chunk.gas_limit)
+            });
+
         Self {
             block_height: block_view.header.height.into(),
             block_hash: block_view.header.hash.to_string(),
@@ -28,12 +48,32 @@ impl Block {
             gas_price: BigDecimal::from_str(block_view.header.gas_price.to_string().as_str())
                 .expect("`gas_price` expected to be u128"),
             author_account_id: block_view.author.to_string(),
+            epoch_id: Some(block_view.header.epoch_id.to_string()),
+            next_epoch_id: Some(block_view.header.next_epoch_id.to_string()),
+            chunks_included: Some(block_view.header.chunks_included.into()),
+            validator_reward: Some(
+                BigDecimal::from_str(block_view.header.validator_reward.to_string().as_str())
+                    .expect("`validator_reward` expected to be u128"),
+            ),
+            latest_protocol_version: Some(block_view.header.latest_protocol_version as i32),
+            block_ordinal: block_view.header.block_ordinal.map(Into::into),
+            random_value: Some(block_view.header.random_value.to_string()),
+            approvals_count: Some(
+                block_view
+                    .header
+                    .approvals
+                    .iter()
+                    .filter(|approval| approval.is_some())
+                    .count() as i32,
+            ),
+            gas_used: Some(gas_used.into()),
+            gas_limit: Some(gas_limit.into()),
         }
     }
 }
 
 impl crate::models::SqlMethods for Block {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.block_height);
         args.add(&self.block_hash);
         args.add(&self.prev_block_hash);
@@ -41,6 +81,16 @@ impl crate::models::SqlMethods for Block {
         args.add(&self.total_supply);
         args.add(&self.gas_price);
         args.add(&self.author_account_id);
+        args.add(&self.epoch_id);
+        args.add(&self.next_epoch_id);
+        args.add(&self.chunks_included);
+        args.add(&self.validator_reward);
+        args.add(&self.latest_protocol_version);
+        args.add(&self.block_ordinal);
+        args.add(&self.random_value);
+        args.add(&self.approvals_count);
+        args.add(&self.gas_used);
+        args.add(&self.gas_limit);
     }
 
     fn insert_query(blocks_count: usize) -> anyhow::Result<String> {
@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use bigdecimal::BigDecimal;
 use sqlx::Arguments;
 
@@ -7,12 +9,14 @@ use crate::models::FieldCount;
 pub struct Chunk {
     pub block_timestamp: BigDecimal,
     pub block_hash: String,
-    pub chunk_hash: String,
-    pub index_in_block: BigDecimal,
-    pub signature: String,
-    pub gas_limit: BigDecimal,
-    pub gas_used: BigDecimal,
-    pub author_account_id: String,
+    pub chunk_hash: Option<String>,
+    pub index_in_block: i32,
+    pub signature: Option<String>,
+    pub gas_limit: Option<BigDecimal>,
+    pub gas_used: Option<BigDecimal>,
+    pub author_account_id: Option<String>,
+    pub balance_burnt: Option<BigDecimal>,
+    pub validator_proposals: Option<serde_json::Value>,
 }
 
 impl Chunk {
@@ -24,18 +28,47 @@ impl Chunk {
         Self {
             block_timestamp: block_timestamp.into(),
             block_hash: block_hash.to_string(),
-            chunk_hash: chunk_view.header.chunk_hash.to_string(),
-            index_in_block: chunk_view.header.shard_id.into(),
-            signature: chunk_view.header.signature.to_string(),
-            gas_limit: chunk_view.header.gas_limit.into(),
-            gas_used: chunk_view.header.gas_used.into(),
-            author_account_id: chunk_view.author.to_string(),
+            chunk_hash: Some(chunk_view.header.chunk_hash.to_string()),
+            index_in_block: chunk_view.header.shard_id as i32,
+            signature: Some(chunk_view.header.signature.to_string()),
+            gas_limit: Some(chunk_view.header.gas_limit.into()),
+            gas_used: Some(chunk_view.header.gas_used.into()),
+            author_account_id: Some(chunk_view.author.to_string()),
+            balance_burnt: Some(
+                BigDecimal::from_str(chunk_view.header.balance_burnt.to_string().as_str())
+                    .expect("`balance_burnt` expected to be u128"),
+            ),
+            validator_proposals: Some(
+                serde_json::to_value(&chunk_view.header.validator_proposals)
+                    .expect("`validator_proposals` expected to be serializable"),
+            ),
+        }
+    }
+
+    /// The shard has no new chunk in this block, so we know only where it should be.
+    /// The block carries the header of the shard's previous chunk, its gas is already counted there
+    pub fn missing(
+        shard_id: near_indexer_primitives::types::ShardId,
+        block_hash: &near_indexer_primitives::CryptoHash,
+        block_timestamp: u64,
+    ) -> Self {
+        Self {
+            block_timestamp: block_timestamp.into(),
+            block_hash: block_hash.to_string(),
+            chunk_hash: None,
+            index_in_block: shard_id as i32,
+            signature: None,
+            gas_limit: None,
+            gas_used: None,
+            author_account_id: None,
+            balance_burnt: None,
+            validator_proposals: None,
         }
     }
 }
 
 impl crate::models::SqlMethods for Chunk {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.block_timestamp);
         args.add(&self.block_hash);
         args.add(&self.chunk_hash);
@@ -44,6 +77,8 @@ impl crate::models::SqlMethods for Chunk {
         args.add(&self.gas_limit);
         args.add(&self.gas_used);
         args.add(&self.author_account_id);
+        args.add(&self.balance_burnt);
+        args.add(&self.validator_proposals);
     }
 
     fn insert_query(chunks_count: usize) -> anyhow::Result<String> {
@@ -0,0 +1,89 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::FieldCount;
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ContractDeployment {
+    pub receipt_id: String,
+    pub index_in_receipt: i32,
+    pub block_timestamp: BigDecimal,
+    pub account_id: String,
+    pub kind: String,
+    pub code_sha256: Option<String>,
+    pub code_size: Option<i32>,
+    pub succeeded: bool,
+}
+
+impl ContractDeployment {
+    /// DEPLOY_CONTRACT action carries only the hash of the code,
+    /// the size is taken from ContractCodeUpdate state change if the deployment succeeded
+    pub fn from_deploy_contract_action(
+        receipt: &near_indexer_primitives::views::ReceiptView,
+        code_hash_base64: &str,
+        code_size: Option<usize>,
+        succeeded: bool,
+        block_timestamp: u64,
+    ) -> Self {
+        Self {
+            receipt_id: receipt.receipt_id.to_string(),
+            // we fill it later because we enumerate all the changes of the receipt
+            index_in_receipt: 0,
+            block_timestamp: block_timestamp.into(),
+            account_id: receipt.receiver_id.to_string(),
+            kind: "DEPLOYMENT".to_string(),
+            // the same encoding as `code_sha256` in DEPLOY_CONTRACT action args
+            code_sha256: Some(hex::encode(
+                base64::decode(code_hash_base64).expect("code expected to be encoded to base64"),
+            )),
+            code_size: code_size.map(|code_size| code_size as i32),
+            succeeded,
+        }
+    }
+
+    /// The code is removed together with the account
+    pub fn from_contract_code_deletion(
+        receipt_id: &near_indexer_primitives::CryptoHash,
+        account_id: &near_indexer_primitives::types::AccountId,
+        block_timestamp: u64,
+    ) -> Self {
+        Self {
+            receipt_id: receipt_id.to_string(),
+            // we fill it later because we enumerate all the changes of the receipt
+            index_in_receipt: 0,
+            block_timestamp: block_timestamp.into(),
+            account_id: account_id.to_string(),
+            kind: "DELETION".to_string(),
+            code_sha256: None,
+            code_size: None,
+            succeeded: true,
+        }
+    }
+}
+
+impl crate::models::SqlMethods for ContractDeployment {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.index_in_receipt);
+        args.add(&self.block_timestamp);
+        args.add(&self.account_id);
+        args.add(&self.kind);
+        args.add(&self.code_sha256);
+        args.add(&self.code_size);
+        args.add(&self.succeeded);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO contract_deployments VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, ContractDeployment::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM contract_deployments WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "contract_deployments".to_string()
+    }
+}
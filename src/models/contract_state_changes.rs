@@ -0,0 +1,90 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::{FieldCount, PrintEnum};
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ContractStateChange {
+    pub account_id: String,
+    pub block_timestamp: BigDecimal,
+    pub block_hash: String,
+    pub caused_by_receipt_id: Option<String>,
+    pub cause: String,
+    pub key_base64: String,
+    pub value_base64: Option<String>,
+    pub chunk_index_in_block: i32,
+    pub index_in_chunk: i32,
+}
+
+impl ContractStateChange {
+    /// Returns None for all the changes except contract storage ones.
+    /// The value is None when the key is deleted
+    pub fn from_state_change_with_cause(
+        state_change_with_cause: &near_indexer_primitives::views::StateChangeWithCauseView,
+        changed_in_block_hash: &near_indexer_primitives::CryptoHash,
+        changed_in_block_timestamp: u64,
+        chunk_index_in_block: i32,
+        index_in_chunk: i32,
+    ) -> Option<Self> {
+        let near_indexer_primitives::views::StateChangeWithCauseView { cause, value } =
+            state_change_with_cause;
+
+        let (account_id, key_base64, value_base64) = match value {
+            near_indexer_primitives::views::StateChangeValueView::DataUpdate {
+                account_id,
+                key,
+                value,
+            } => (account_id, base64::encode(key), Some(base64::encode(value))),
+            near_indexer_primitives::views::StateChangeValueView::DataDeletion {
+                account_id,
+                key,
+            } => (account_id, base64::encode(key), None),
+            _ => return None,
+        };
+
+        Some(Self {
+            account_id: account_id.to_string(),
+            block_timestamp: changed_in_block_timestamp.into(),
+            block_hash: changed_in_block_hash.to_string(),
+            caused_by_receipt_id: match cause {
+                near_indexer_primitives::views::StateChangeCauseView::ReceiptProcessing {
+                    receipt_hash,
+                } => Some(receipt_hash.to_string()),
+                _ => None,
+            },
+            cause: cause.print().to_string(),
+            key_base64,
+            value_base64,
+            chunk_index_in_block,
+            index_in_chunk,
+        })
+    }
+}
+
+impl crate::models::SqlMethods for ContractStateChange {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.account_id);
+        args.add(&self.block_timestamp);
+        args.add(&self.block_hash);
+        args.add(&self.caused_by_receipt_id);
+        args.add(&self.cause);
+        args.add(&self.key_base64);
+        args.add(&self.value_base64);
+        args.add(&self.chunk_index_in_block);
+        args.add(&self.index_in_chunk);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO contract_state_changes VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, ContractStateChange::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM contract_state_changes WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "contract_state_changes".to_string()
+    }
+}
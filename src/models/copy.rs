@@ -0,0 +1,291 @@
+use sqlx::Encode;
+
+use crate::models::{DbHandle, SqlMethods};
+
+// https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4
+const COPY_BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Collects the rows in the binary format of `COPY ... FROM STDIN (FORMAT binary)`.
+/// Each value is encoded the same way sqlx encodes the parameters of INSERT query,
+/// so the models fill it with their usual `add_to_args`
+#[derive(Default)]
+pub(crate) struct CopyArguments {
+    buffer: Vec<u8>,
+    fields_in_row: i16,
+}
+
+impl CopyArguments {
+    pub(crate) fn from_items<T: SqlMethods>(items: &[T]) -> Self {
+        let mut copy_args = Self::default();
+        copy_args.buffer.extend_from_slice(COPY_BINARY_SIGNATURE);
+        // Flags field
+        copy_args.buffer.extend_from_slice(&0i32.to_be_bytes());
+        // Header extension area length
+        copy_args.buffer.extend_from_slice(&0i32.to_be_bytes());
+
+        for item in items {
+            let row_start = copy_args.buffer.len();
+            // Number of fields in the row, we fill it when the row is ready
+            copy_args.buffer.extend_from_slice(&0i16.to_be_bytes());
+            copy_args.fields_in_row = 0;
+            item.add_to_args(&mut copy_args);
+            let fields_in_row = copy_args.fields_in_row.to_be_bytes();
+            copy_args.buffer[row_start..row_start + 2].copy_from_slice(&fields_in_row);
+        }
+
+        // File trailer
+        copy_args.buffer.extend_from_slice(&(-1i16).to_be_bytes());
+        copy_args
+    }
+}
+
+impl<'q> sqlx::Arguments<'q> for CopyArguments {
+    type Database = sqlx::Postgres;
+
+    fn reserve(&mut self, _additional: usize, size: usize) {
+        self.buffer.reserve(size);
+    }
+
+    fn add<T>(&mut self, value: T)
+    where
+        T: 'q + Send + Encode<'q, Self::Database> + sqlx::Type<Self::Database>,
+    {
+        let mut value_buffer = sqlx::postgres::PgArgumentBuffer::default();
+        let len = match value.encode(&mut value_buffer) {
+            sqlx::encode::IsNull::No => value_buffer.len() as i32,
+            // -1 means NULL
+            sqlx::encode::IsNull::Yes => -1,
+        };
+        self.buffer.extend_from_slice(&len.to_be_bytes());
+        self.buffer.extend_from_slice(&value_buffer);
+        self.fields_in_row += 1;
+    }
+}
+
+/// COPY can't skip the rows which are already stored, so we load the data to the temporary table
+/// and then move it to the destination table with `ON CONFLICT DO NOTHING`
+pub(crate) async fn copy_via_staging_table(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    table_name: &str,
+    data: &[u8],
+) -> Result<(), sqlx::Error> {
+    let staging_table_name = format!("_staging_{}", table_name);
+
+    sqlx::query(&format!(
+        "CREATE TEMP TABLE {} (LIKE {}) ON COMMIT DROP",
+        staging_table_name, table_name
+    ))
+    .execute(&mut *transaction)
+    .await?;
+
+    let mut copy_in = transaction
+        .copy_in_raw(&format!(
+            "COPY {} FROM STDIN (FORMAT binary)",
+            staging_table_name
+        ))
+        .await?;
+    let sent = copy_in.send(data).await.map(|_| ());
+    match sent {
+        Ok(_) => {
+            copy_in.finish().await?;
+        }
+        Err(async_error) => {
+            copy_in.abort(async_error.to_string()).await?;
+            return Err(async_error);
+        }
+    }
+
+    sqlx::query(&format!(
+        "INSERT INTO {} SELECT * FROM {} ON CONFLICT DO NOTHING",
+        table_name, staging_table_name
+    ))
+    .execute(&mut *transaction)
+    .await?;
+
+    // The name could be used again before the commit if we are inside the bigger transaction
+    sqlx::query(&format!("DROP TABLE {}", staging_table_name))
+        .execute(&mut *transaction)
+        .await?;
+
+    Ok(())
+}
+
+pub(crate) async fn copy_retry_or_panic<T: SqlMethods + std::fmt::Debug>(
+    db: DbHandle<'_>,
+    items: &[T],
+    retry_count: usize,
+) -> anyhow::Result<()> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let mut interval = crate::INTERVAL;
+    let mut retry_attempt = 0usize;
+    let copy_args = CopyArguments::from_items(items);
+
+    loop {
+        if retry_attempt == retry_count {
+            return Err(anyhow::anyhow!(
+                "Failed to perform COPY to database after {} attempts. Stop trying.",
+                retry_count
+            ));
+        }
+        retry_attempt += 1;
+
+        let timer = crate::metrics::DB_QUERY_DURATION_SECONDS
+            .with_label_values(&[&T::name()])
+            .start_timer();
+        let result = db.copy_in(&T::name(), &copy_args.buffer).await;
+        timer.observe_duration();
+
+        match result {
+            Ok(_) => {
+                crate::metrics::ROWS_WRITTEN
+                    .with_label_values(&[&T::name()])
+                    .inc_by(items.len() as u64);
+                break;
+            }
+            Err(async_error) => {
+                crate::metrics::DB_RETRIES
+                    .with_label_values(&[&T::name()])
+                    .inc();
+                eprintln!(
+                    "Error occurred during {}:\n{} were not copied. \n{:#?} \n Retrying in {} milliseconds...",
+                    async_error,
+                    &T::name(),
+                    &items,
+                    interval.as_millis(),
+                );
+                tokio::time::sleep(interval).await;
+                if interval < crate::MAX_DELAY_TIME {
+                    interval *= 2;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use bigdecimal::BigDecimal;
+    use sqlx::Arguments;
+
+    use super::*;
+
+    struct TestRow {
+        memo: Option<String>,
+        amount: BigDecimal,
+        args: serde_json::Value,
+    }
+
+    impl SqlMethods for TestRow {
+        fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+            args.add(&self.memo);
+            args.add(&self.amount);
+            args.add(&self.args);
+        }
+
+        fn insert_query(_items_count: usize) -> anyhow::Result<String> {
+            unreachable!()
+        }
+
+        fn delete_query() -> String {
+            unreachable!()
+        }
+
+        fn name() -> String {
+            "test_rows".to_string()
+        }
+    }
+
+    fn header() -> Vec<u8> {
+        let mut header = COPY_BINARY_SIGNATURE.to_vec();
+        header.extend_from_slice(&[0, 0, 0, 0]);
+        header.extend_from_slice(&[0, 0, 0, 0]);
+        header
+    }
+
+    #[test]
+    fn empty_copy_has_header_and_trailer() {
+        let copy_args = CopyArguments::from_items::<TestRow>(&[]);
+
+        let mut expected = header();
+        expected.extend_from_slice(&[0xff, 0xff]);
+        assert_eq!(copy_args.buffer, expected);
+    }
+
+    #[test]
+    fn row_is_encoded_with_null_numeric_and_jsonb() {
+        let copy_args = CopyArguments::from_items(&[TestRow {
+            memo: None,
+            amount: BigDecimal::from_str("12345").unwrap(),
+            args: serde_json::json!({"a": 1}),
+        }]);
+
+        let mut expected = header();
+        // Number of fields
+        expected.extend_from_slice(&[0, 3]);
+        // NULL has -1 length and no data
+        expected.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        // numeric: 2 base-10000 digits, weight 1, positive, scale 0, digits 1 and 2345
+        expected.extend_from_slice(&[0, 0, 0, 12]);
+        expected.extend_from_slice(&[0, 2, 0, 1, 0, 0, 0, 0, 0, 1, 0x09, 0x29]);
+        // jsonb: version byte 1 and the text of the value
+        expected.extend_from_slice(&[0, 0, 0, 8, 1]);
+        expected.extend_from_slice(br#"{"a":1}"#);
+        expected.extend_from_slice(&[0xff, 0xff]);
+        assert_eq!(copy_args.buffer, expected);
+    }
+
+    #[test]
+    fn negative_fractional_numeric_keeps_sign_and_scale() {
+        let copy_args = CopyArguments::from_items(&[TestRow {
+            memo: Some("a".to_string()),
+            amount: BigDecimal::from_str("-1.5").unwrap(),
+            args: serde_json::Value::Null,
+        }]);
+
+        let row = &copy_args.buffer[header().len()..];
+        // Number of fields and the text "a"
+        assert_eq!(&row[..7], &[0, 3, 0, 0, 0, 1, b'a']);
+        // numeric: 2 digits, weight 0, negative sign 0x4000, scale 1, digits 1 and 5000
+        assert_eq!(
+            &row[7..23],
+            &[0, 0, 0, 12, 0, 2, 0, 0, 0x40, 0, 0, 1, 0, 1, 0x13, 0x88]
+        );
+    }
+
+    #[test]
+    fn each_row_has_its_own_field_count() {
+        let row = TestRow {
+            memo: None,
+            amount: BigDecimal::from(0),
+            args: serde_json::Value::Null,
+        };
+        let copy_args = CopyArguments::from_items(&[row]);
+        let one_row_len = copy_args.buffer.len() - header().len() - 2;
+
+        let rows = [
+            TestRow {
+                memo: None,
+                amount: BigDecimal::from(0),
+                args: serde_json::Value::Null,
+            },
+            TestRow {
+                memo: None,
+                amount: BigDecimal::from(0),
+                args: serde_json::Value::Null,
+            },
+        ];
+        let copy_args = CopyArguments::from_items(&rows);
+        let second_row_start = header().len() + one_row_len;
+        assert_eq!(copy_args.buffer.len(), header().len() + 2 * one_row_len + 2);
+        assert_eq!(
+            &copy_args.buffer[second_row_start..second_row_start + 2],
+            &[0, 3]
+        );
+    }
+}
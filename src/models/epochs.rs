@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::FieldCount;
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct Epoch {
+    pub epoch_id: String,
+    pub next_epoch_id: String,
+    pub start_block_height: BigDecimal,
+    pub start_block_timestamp: BigDecimal,
+    pub end_block_height: BigDecimal,
+    pub end_block_timestamp: BigDecimal,
+}
+
+impl Epoch {
+    /// Each block could be the first or the last one of its epoch,
+    /// the bounds are widened when the row already exists
+    pub fn from_block_view(block_view: &near_indexer_primitives::views::BlockView) -> Self {
+        Self {
+            epoch_id: block_view.header.epoch_id.to_string(),
+            next_epoch_id: block_view.header.next_epoch_id.to_string(),
+            start_block_height: block_view.header.height.into(),
+            start_block_timestamp: block_view.header.timestamp.into(),
+            end_block_height: block_view.header.height.into(),
+            end_block_timestamp: block_view.header.timestamp.into(),
+        }
+    }
+}
+
+impl crate::models::SqlMethods for Epoch {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.epoch_id);
+        args.add(&self.next_epoch_id);
+        args.add(&self.start_block_height);
+        args.add(&self.start_block_timestamp);
+        args.add(&self.end_block_height);
+        args.add(&self.end_block_timestamp);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO epochs VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, Epoch::field_count())?
+            + " ON CONFLICT (epoch_id) DO UPDATE SET
+                 start_block_height = LEAST(epochs.start_block_height, EXCLUDED.start_block_height),
+                 start_block_timestamp = LEAST(epochs.start_block_timestamp, EXCLUDED.start_block_timestamp),
+                 end_block_height = GREATEST(epochs.end_block_height, EXCLUDED.end_block_height),
+                 end_block_timestamp = GREATEST(epochs.end_block_timestamp, EXCLUDED.end_block_timestamp)")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM epochs WHERE start_block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "epochs".to_string()
+    }
+}
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct Validator {
+    pub epoch_id: String,
+    pub account_id: String,
+    pub produced_blocks: i32,
+    pub produced_chunks: i32,
+    pub proposed_stake: Option<BigDecimal>,
+    pub last_block_height: BigDecimal,
+}
+
+impl Validator {
+    fn new(block_view: &near_indexer_primitives::views::BlockView, account_id: String) -> Self {
+        Self {
+            epoch_id: block_view.header.epoch_id.to_string(),
+            account_id,
+            produced_blocks: 0,
+            produced_chunks: 0,
+            proposed_stake: None,
+            last_block_height: block_view.header.height.into(),
+        }
+    }
+
+    /// Collects the block producer, the chunk producers of all the shards
+    /// and the validator proposals of the block, one row per account
+    pub fn from_block(
+        block_view: &near_indexer_primitives::views::BlockView,
+        shards: &[near_indexer_primitives::IndexerShard],
+    ) -> Vec<Self> {
+        let mut validators: HashMap<String, Self> = HashMap::new();
+
+        validators
+            .entry(block_view.author.to_string())
+            .or_insert_with_key(|account_id| Self::new(block_view, account_id.clone()))
+            .produced_blocks += 1;
+
+        for chunk in shards.iter().filter_map(|shard| shard.chunk.as_ref()) {
+            validators
+                .entry(chunk.author.to_string())
+                .or_insert_with_key(|account_id| Self::new(block_view, account_id.clone()))
+                .produced_chunks += 1;
+        }
+
+        for proposal in &block_view.header.validator_proposals {
+            let (account_id, stake) = proposal.clone().into_validator_stake().account_and_stake();
+            validators
+                .entry(account_id.to_string())
+                .or_insert_with_key(|account_id| Self::new(block_view, account_id.clone()))
+                .proposed_stake = Some(
+                BigDecimal::from_str(stake.to_string().as_str())
+                    .expect("`stake` expected to be u128"),
+            );
+        }
+
+        validators.into_values().collect()
+    }
+}
+
+impl crate::models::SqlMethods for Validator {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.epoch_id);
+        args.add(&self.account_id);
+        args.add(&self.produced_blocks);
+        args.add(&self.produced_chunks);
+        args.add(&self.proposed_stake);
+        args.add(&self.last_block_height);
+    }
+
+    /// The counters are summed up block by block, so each block is counted only once:
+    /// the blocks come here in their order, and the block which was already counted is skipped
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO validators VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, Validator::field_count())?
+            + " ON CONFLICT (epoch_id, account_id) DO UPDATE SET
+                 produced_blocks = validators.produced_blocks + EXCLUDED.produced_blocks,
+                 produced_chunks = validators.produced_chunks + EXCLUDED.produced_chunks,
+                 proposed_stake = COALESCE(EXCLUDED.proposed_stake, validators.proposed_stake),
+                 last_block_height = EXCLUDED.last_block_height
+               WHERE validators.last_block_height < EXCLUDED.last_block_height")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM validators
+         WHERE epoch_id IN (SELECT epoch_id FROM epochs WHERE start_block_timestamp >= $1)"
+            .to_string()
+    }
+
+    fn name() -> String {
+        "validators".to_string()
+    }
+}
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ValidatorShard {
+    pub epoch_id: String,
+    pub account_id: String,
+    pub shard_id: i32,
+    pub produced_chunks: i32,
+    pub last_block_height: BigDecimal,
+}
+
+impl ValidatorShard {
+    /// Collects the chunk producers of the block, one row per shard with the new chunk
+    pub fn from_block(
+        block_view: &near_indexer_primitives::views::BlockView,
+        shards: &[near_indexer_primitives::IndexerShard],
+    ) -> Vec<Self> {
+        shards
+            .iter()
+            .filter_map(|shard| shard.chunk.as_ref())
+            .map(|chunk| Self {
+                epoch_id: block_view.header.epoch_id.to_string(),
+                account_id: chunk.author.to_string(),
+                shard_id: chunk.header.shard_id as i32,
+                produced_chunks: 1,
+                last_block_height: block_view.header.height.into(),
+            })
+            .collect()
+    }
+}
+
+impl crate::models::SqlMethods for ValidatorShard {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.epoch_id);
+        args.add(&self.account_id);
+        args.add(&self.shard_id);
+        args.add(&self.produced_chunks);
+        args.add(&self.last_block_height);
+    }
+
+    /// Same as for validators, each block is counted only once
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO validator_shards VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, ValidatorShard::field_count())?
+            + " ON CONFLICT (epoch_id, account_id, shard_id) DO UPDATE SET
+                 produced_chunks = validator_shards.produced_chunks + EXCLUDED.produced_chunks,
+                 last_block_height = EXCLUDED.last_block_height
+               WHERE validator_shards.last_block_height < EXCLUDED.last_block_height")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM validator_shards
+         WHERE epoch_id IN (SELECT epoch_id FROM epochs WHERE start_block_timestamp >= $1)"
+            .to_string()
+    }
+
+    fn name() -> String {
+        "validator_shards".to_string()
+    }
+}
@@ -16,6 +16,9 @@ pub struct ExecutionOutcome {
     pub tokens_burnt: BigDecimal,
     pub executor_account_id: String,
     pub status: String,
+    pub success_value_base64: Option<String>,
+    pub success_value_json: Option<serde_json::Value>,
+    pub failure: Option<serde_json::Value>,
 }
 
 impl ExecutionOutcome {
@@ -25,7 +28,7 @@ impl ExecutionOutcome {
         executed_in_block_timestamp: u64,
         shard_id: u64,
     ) -> Self {
-        Self {
+        let mut outcome = Self {
             receipt_id: execution_outcome.id.to_string(),
             block_hash: execution_outcome.block_hash.to_string(),
             block_timestamp: executed_in_block_timestamp.into(),
@@ -38,12 +41,37 @@ impl ExecutionOutcome {
             .expect("`tokens_burnt` expected to be u128"),
             executor_account_id: execution_outcome.outcome.executor_id.to_string(),
             status: execution_outcome.outcome.status.print().to_string(),
+            success_value_base64: None,
+            success_value_json: None,
+            failure: None,
+        };
+
+        match &execution_outcome.outcome.status {
+            near_indexer_primitives::views::ExecutionStatusView::SuccessValue(value) => {
+                // The value is stored as it is, and also as JSON if it's possible to decode it
+                if let Ok(decoded_value) = base64::decode(value) {
+                    if let Ok(mut value_json) = serde_json::from_slice(&decoded_value) {
+                        crate::models::serializers::escape_json(&mut value_json);
+                        outcome.success_value_json = Some(value_json);
+                    }
+                }
+                outcome.success_value_base64 = Some(value.clone());
+            }
+            near_indexer_primitives::views::ExecutionStatusView::Failure(error) => {
+                let mut failure = serde_json::to_value(error)
+                    .expect("TxExecutionError expected to be serializable");
+                crate::models::serializers::escape_json(&mut failure);
+                outcome.failure = Some(failure);
+            }
+            _ => {}
         }
+
+        outcome
     }
 }
 
 impl crate::models::SqlMethods for ExecutionOutcome {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.receipt_id);
         args.add(&self.block_hash);
         args.add(&self.block_timestamp);
@@ -53,6 +81,9 @@ impl crate::models::SqlMethods for ExecutionOutcome {
         args.add(&self.tokens_burnt);
         args.add(&self.executor_account_id);
         args.add(&self.status);
+        args.add(&self.success_value_base64);
+        args.add(&self.success_value_json);
+        args.add(&self.failure);
     }
 
     fn insert_query(execution_outcome_count: usize) -> anyhow::Result<String> {
@@ -81,16 +112,18 @@ pub struct ExecutionOutcomeReceipt {
     pub produced_receipt_id: String,
     pub chunk_index_in_block: i32,
     pub index_in_chunk: i32,
+    pub index_in_outcome: i32,
 }
 
 impl crate::models::SqlMethods for ExecutionOutcomeReceipt {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.block_hash);
         args.add(&self.block_timestamp);
         args.add(&self.executed_receipt_id);
         args.add(&self.produced_receipt_id);
         args.add(&self.chunk_index_in_block);
         args.add(&self.index_in_chunk);
+        args.add(&self.index_in_outcome);
     }
 
     fn insert_query(execution_outcome_receipt_count: usize) -> anyhow::Result<String> {
@@ -112,3 +145,199 @@ impl crate::models::SqlMethods for ExecutionOutcomeReceipt {
         "execution_outcomes__receipts".to_string()
     }
 }
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ExecutionOutcomeLog {
+    pub receipt_id: String,
+    pub log_index: i32,
+    pub block_hash: String,
+    pub block_timestamp: BigDecimal,
+    pub executor_account_id: String,
+    pub log: String,
+    pub standard: Option<String>,
+    pub version: Option<String>,
+    pub event: Option<String>,
+    pub data: Option<serde_json::Value>,
+}
+
+impl ExecutionOutcomeLog {
+    pub fn from_log(
+        execution_outcome: &near_indexer_primitives::views::ExecutionOutcomeWithIdView,
+        log: &str,
+        log_index: i32,
+        executed_in_block_timestamp: u64,
+    ) -> Self {
+        let event_log = crate::models::serializers::parse_event_log(log);
+        let (standard, version, event, data) = match event_log {
+            Some(event_log) => (
+                Some(crate::models::serializers::escape_nul(&event_log.standard)),
+                Some(crate::models::serializers::escape_nul(&event_log.version)),
+                Some(crate::models::serializers::escape_nul(&event_log.event)),
+                event_log.data.map(|mut data| {
+                    crate::models::serializers::escape_json(&mut data);
+                    data
+                }),
+            ),
+            None => (None, None, None, None),
+        };
+
+        Self {
+            receipt_id: execution_outcome.id.to_string(),
+            log_index,
+            block_hash: execution_outcome.block_hash.to_string(),
+            block_timestamp: executed_in_block_timestamp.into(),
+            executor_account_id: execution_outcome.outcome.executor_id.to_string(),
+            log: crate::models::serializers::escape_nul(log),
+            standard,
+            version,
+            event,
+            data,
+        }
+    }
+}
+
+impl crate::models::SqlMethods for ExecutionOutcomeLog {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.log_index);
+        args.add(&self.block_hash);
+        args.add(&self.block_timestamp);
+        args.add(&self.executor_account_id);
+        args.add(&self.log);
+        args.add(&self.standard);
+        args.add(&self.version);
+        args.add(&self.event);
+        args.add(&self.data);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO execution_outcome_logs VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, ExecutionOutcomeLog::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM execution_outcome_logs WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "execution_outcome_logs".to_string()
+    }
+}
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ExecutionOutcomeGasProfile {
+    pub receipt_id: String,
+    pub index_in_profile: i32,
+    pub block_timestamp: BigDecimal,
+    pub executor_account_id: String,
+    pub cost_category: String,
+    pub cost: String,
+    pub gas_used: BigDecimal,
+}
+
+impl ExecutionOutcomeGasProfile {
+    /// The gas profile is missing in the old execution outcomes, we have nothing to store then
+    pub fn from_execution_outcome(
+        execution_outcome: &near_indexer_primitives::views::ExecutionOutcomeWithIdView,
+        executed_in_block_timestamp: u64,
+    ) -> Vec<Self> {
+        execution_outcome
+            .outcome
+            .metadata
+            .gas_profile
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index_in_profile, cost_gas_used)| Self {
+                receipt_id: execution_outcome.id.to_string(),
+                index_in_profile: index_in_profile as i32,
+                block_timestamp: executed_in_block_timestamp.into(),
+                executor_account_id: execution_outcome.outcome.executor_id.to_string(),
+                cost_category: cost_gas_used.cost_category.clone(),
+                cost: cost_gas_used.cost.clone(),
+                gas_used: cost_gas_used.gas_used.into(),
+            })
+            .collect()
+    }
+}
+
+impl crate::models::SqlMethods for ExecutionOutcomeGasProfile {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.index_in_profile);
+        args.add(&self.block_timestamp);
+        args.add(&self.executor_account_id);
+        args.add(&self.cost_category);
+        args.add(&self.cost);
+        args.add(&self.gas_used);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok(
+            "INSERT INTO execution_outcome_gas_profiles VALUES ".to_owned()
+                + &crate::models::create_placeholders(
+                    items_count,
+                    ExecutionOutcomeGasProfile::field_count(),
+                )?
+                + " ON CONFLICT DO NOTHING",
+        )
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM execution_outcome_gas_profiles WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "execution_outcome_gas_profiles".to_string()
+    }
+}
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct ExecutionOutcomeProof {
+    pub receipt_id: String,
+    pub block_hash: String,
+    pub block_timestamp: BigDecimal,
+    pub proof: serde_json::Value,
+}
+
+impl ExecutionOutcomeProof {
+    pub fn from_execution_outcome(
+        execution_outcome: &near_indexer_primitives::views::ExecutionOutcomeWithIdView,
+        executed_in_block_timestamp: u64,
+    ) -> Self {
+        Self {
+            receipt_id: execution_outcome.id.to_string(),
+            block_hash: execution_outcome.block_hash.to_string(),
+            block_timestamp: executed_in_block_timestamp.into(),
+            proof: serde_json::to_value(&execution_outcome.proof)
+                .expect("`proof` expected to be serializable"),
+        }
+    }
+}
+
+impl crate::models::SqlMethods for ExecutionOutcomeProof {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.block_hash);
+        args.add(&self.block_timestamp);
+        args.add(&self.proof);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO execution_outcome_proofs VALUES ".to_owned()
+            + &crate::models::create_placeholders(
+                items_count,
+                ExecutionOutcomeProof::field_count(),
+            )?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM execution_outcome_proofs WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "execution_outcome_proofs".to_string()
+    }
+}
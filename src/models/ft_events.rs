@@ -0,0 +1,151 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use sqlx::Arguments;
+
+use crate::models::serializers::escape_nul;
+use crate::models::FieldCount;
+
+/// One row per account affected by the event: `ft_transfer` gives two rows,
+/// the negative delta for the sender and the positive one for the receiver
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct FtEvent {
+    pub receipt_id: String,
+    pub event_index: i32,
+    pub block_timestamp: BigDecimal,
+    pub contract_account_id: String,
+    pub affected_account_id: String,
+    pub involved_account_id: Option<String>,
+    pub delta_amount: BigDecimal,
+    pub cause: String,
+    pub memo: Option<String>,
+}
+
+// https://nomicon.io/Standards/Tokens/FungibleToken/Event
+#[derive(Deserialize, Debug)]
+struct FtMintOrBurnData {
+    owner_id: String,
+    amount: String,
+    memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct FtTransferData {
+    old_owner_id: String,
+    new_owner_id: String,
+    amount: String,
+    memo: Option<String>,
+}
+
+impl FtEvent {
+    /// Returns nothing for the events of other standards and for the malformed ones
+    pub fn from_event_log(
+        event_log: &crate::models::serializers::EventLog,
+        receipt_id: &near_indexer_primitives::CryptoHash,
+        contract_account_id: &near_indexer_primitives::types::AccountId,
+        block_timestamp: u64,
+    ) -> Vec<Self> {
+        if event_log.standard != "nep141" {
+            return vec![];
+        }
+        let data = match &event_log.data {
+            Some(data) => data.clone(),
+            None => return vec![],
+        };
+
+        let event = |affected_account_id: String,
+                     involved_account_id: Option<String>,
+                     delta_amount: BigDecimal,
+                     cause: &str,
+                     memo: Option<String>| Self {
+            receipt_id: receipt_id.to_string(),
+            // we fill it later because we enumerate all the events of the receipt
+            event_index: 0,
+            block_timestamp: block_timestamp.into(),
+            contract_account_id: contract_account_id.to_string(),
+            affected_account_id: escape_nul(&affected_account_id),
+            involved_account_id: involved_account_id.as_deref().map(escape_nul),
+            delta_amount,
+            cause: cause.to_string(),
+            memo: memo.as_deref().map(escape_nul),
+        };
+
+        match event_log.event.as_str() {
+            "ft_mint" | "ft_burn" => {
+                let is_mint = event_log.event == "ft_mint";
+                serde_json::from_value::<Vec<FtMintOrBurnData>>(data)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|data| {
+                        let amount = parse_amount(&data.amount)?;
+                        Some(if is_mint {
+                            event(data.owner_id, None, amount, "MINT", data.memo)
+                        } else {
+                            event(data.owner_id, None, -amount, "BURN", data.memo)
+                        })
+                    })
+                    .collect()
+            }
+            "ft_transfer" => serde_json::from_value::<Vec<FtTransferData>>(data)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|data| {
+                    let amount = parse_amount(&data.amount)?;
+                    Some([
+                        event(
+                            data.old_owner_id.clone(),
+                            Some(data.new_owner_id.clone()),
+                            -amount.clone(),
+                            "TRANSFER",
+                            data.memo.clone(),
+                        ),
+                        event(
+                            data.new_owner_id,
+                            Some(data.old_owner_id),
+                            amount,
+                            "TRANSFER",
+                            data.memo,
+                        ),
+                    ])
+                })
+                .flatten()
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// The amount is u128 in the string, we skip the events which break this rule
+fn parse_amount(amount: &str) -> Option<BigDecimal> {
+    let amount = amount.parse::<u128>().ok()?;
+    Some(BigDecimal::from_str(&amount.to_string()).expect("u128 expected to be BigDecimal"))
+}
+
+impl crate::models::SqlMethods for FtEvent {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.event_index);
+        args.add(&self.block_timestamp);
+        args.add(&self.contract_account_id);
+        args.add(&self.affected_account_id);
+        args.add(&self.involved_account_id);
+        args.add(&self.delta_amount);
+        args.add(&self.cause);
+        args.add(&self.memo);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO ft_events VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, FtEvent::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM ft_events WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "ft_events".to_string()
+    }
+}
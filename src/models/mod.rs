@@ -1,25 +1,46 @@
 use futures::future::try_join_all;
-use sqlx::Arguments;
+use sqlx::{Acquire, Arguments};
 use std::fmt::Write;
+use tokio::sync::Mutex;
 
 use near_indexer_primitives::views::{
-    AccessKeyPermissionView, ExecutionStatusView, StateChangeCauseView,
+    AccessKeyPermissionView, ExecutionStatusView, StateChangeCauseView, StateChangeValueView,
 };
 
+pub(crate) use access_keys::AccessKey;
 pub(crate) use account_changes::AccountChange;
+pub(crate) use accounts::Account;
 pub(crate) use blocks::Block;
 pub(crate) use chunks::Chunk;
-pub(crate) use execution_outcomes::{ExecutionOutcome, ExecutionOutcomeReceipt};
+pub(crate) use contract_deployments::ContractDeployment;
+pub(crate) use contract_state_changes::ContractStateChange;
+pub(crate) use epochs::{Epoch, Validator, ValidatorShard};
+pub(crate) use execution_outcomes::{
+    ExecutionOutcome, ExecutionOutcomeGasProfile, ExecutionOutcomeLog, ExecutionOutcomeProof,
+    ExecutionOutcomeReceipt,
+};
+pub(crate) use ft_events::FtEvent;
 pub(crate) use indexer_base::FieldCount;
+pub(crate) use nft_events::NftEvent;
 pub(crate) use receipts::{ActionReceipt, ActionReceiptAction, ActionReceiptsOutput, DataReceipt};
-pub(crate) use transactions::Transaction;
+pub(crate) use state_changes::StateChange;
+pub(crate) use transactions::{Transaction, TransactionAction};
 
+pub(crate) mod access_keys;
 pub(crate) mod account_changes;
+pub(crate) mod accounts;
 pub(crate) mod blocks;
 pub(crate) mod chunks;
+pub(crate) mod contract_deployments;
+pub(crate) mod contract_state_changes;
+pub(crate) mod copy;
+pub(crate) mod epochs;
 pub(crate) mod execution_outcomes;
+pub(crate) mod ft_events;
+pub(crate) mod nft_events;
 pub(crate) mod receipts;
 pub(crate) mod serializers;
+pub(crate) mod state_changes;
 pub(crate) mod transactions;
 
 pub trait FieldCount {
@@ -28,7 +49,9 @@ pub trait FieldCount {
 }
 
 pub trait SqlMethods {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments);
+    /// Adds the values in the order of the table columns.
+    /// The arguments are either the parameters of INSERT query or the row of COPY
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A);
 
     fn insert_query(count: usize) -> anyhow::Result<String>;
 
@@ -37,18 +60,107 @@ pub trait SqlMethods {
     fn name() -> String;
 }
 
+/// The place where the queries are executed
+#[derive(Clone, Copy)]
+pub enum DbConnection<'a> {
+    /// Each query goes to the pool on its own
+    Pool(&'a sqlx::Pool<sqlx::Postgres>),
+    /// All the queries go through one transaction, so the readers never see partially stored blocks.
+    /// The concurrent writers wait for each other on the Mutex
+    Transaction(&'a Mutex<sqlx::Transaction<'static, sqlx::Postgres>>),
+}
+
+/// Everything the db_adapters need to know to talk to the database
+#[derive(Clone, Copy)]
+pub struct DbHandle<'a> {
+    pub connection: DbConnection<'a>,
+    pub write_mode: crate::configs::WriteMode,
+}
+
+impl DbHandle<'_> {
+    pub(crate) async fn execute(
+        &self,
+        query: &str,
+        args: sqlx::postgres::PgArguments,
+    ) -> Result<(), sqlx::Error> {
+        match self.connection {
+            DbConnection::Pool(pool) => sqlx::query_with(query, args)
+                .execute(pool)
+                .await
+                .map(|_| ()),
+            DbConnection::Transaction(transaction) => {
+                let mut transaction = transaction.lock().await;
+                // Failed query aborts the whole transaction.
+                // Savepoint allows us to roll back only this query and retry it
+                let mut savepoint = transaction.begin().await?;
+                sqlx::query_with(query, args)
+                    .execute(&mut savepoint)
+                    .await?;
+                savepoint.commit().await
+            }
+        }
+    }
+
+    pub(crate) async fn fetch_all(
+        &self,
+        query: &str,
+        args: sqlx::postgres::PgArguments,
+    ) -> Result<Vec<sqlx::postgres::PgRow>, sqlx::Error> {
+        match self.connection {
+            DbConnection::Pool(pool) => sqlx::query_with(query, args).fetch_all(pool).await,
+            DbConnection::Transaction(transaction) => {
+                let mut transaction = transaction.lock().await;
+                let mut savepoint = transaction.begin().await?;
+                let rows = sqlx::query_with(query, args)
+                    .fetch_all(&mut savepoint)
+                    .await?;
+                savepoint.commit().await?;
+                Ok(rows)
+            }
+        }
+    }
+
+    /// Loads the rows encoded by [copy::CopyArguments] to the given table
+    pub(crate) async fn copy_in(&self, table_name: &str, data: &[u8]) -> Result<(), sqlx::Error> {
+        match self.connection {
+            DbConnection::Pool(pool) => {
+                // Staging table lives only inside this transaction
+                let mut transaction = pool.begin().await?;
+                copy::copy_via_staging_table(&mut transaction, table_name, data).await?;
+                transaction.commit().await
+            }
+            DbConnection::Transaction(transaction) => {
+                let mut transaction = transaction.lock().await;
+                let mut savepoint = transaction.begin().await?;
+                copy::copy_via_staging_table(&mut savepoint, table_name, data).await?;
+                savepoint.commit().await
+            }
+        }
+    }
+}
+
 pub async fn chunked_insert<T: SqlMethods + std::fmt::Debug>(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: DbHandle<'_>,
     items: &[T],
 ) -> anyhow::Result<()> {
-    let futures = items
-        .chunks(crate::db_adapters::CHUNK_SIZE_FOR_BATCH_INSERT)
-        .map(|items_part| insert_retry_or_panic(pool, items_part, crate::db_adapters::RETRY_COUNT));
-    try_join_all(futures).await.map(|_| ())
+    match db.write_mode {
+        crate::configs::WriteMode::Insert => {
+            let futures = items
+                .chunks(crate::db_adapters::CHUNK_SIZE_FOR_BATCH_INSERT)
+                .map(|items_part| {
+                    insert_retry_or_panic(db, items_part, crate::db_adapters::RETRY_COUNT)
+                });
+            try_join_all(futures).await.map(|_| ())
+        }
+        // COPY has no limit for the number of the parameters, so we load all the items at once
+        crate::configs::WriteMode::Copy => {
+            copy::copy_retry_or_panic(db, items, crate::db_adapters::RETRY_COUNT).await
+        }
+    }
 }
 
 async fn insert_retry_or_panic<T: SqlMethods + std::fmt::Debug>(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: DbHandle<'_>,
     items: &[T],
     retry_count: usize,
 ) -> anyhow::Result<()> {
@@ -70,9 +182,23 @@ async fn insert_retry_or_panic<T: SqlMethods + std::fmt::Debug>(
             item.add_to_args(&mut args);
         }
 
-        match sqlx::query_with(&query, args).execute(pool).await {
-            Ok(_) => break,
+        let timer = crate::metrics::DB_QUERY_DURATION_SECONDS
+            .with_label_values(&[&T::name()])
+            .start_timer();
+        let result = db.execute(&query, args).await;
+        timer.observe_duration();
+
+        match result {
+            Ok(_) => {
+                crate::metrics::ROWS_WRITTEN
+                    .with_label_values(&[&T::name()])
+                    .inc_by(items.len() as u64);
+                break;
+            }
             Err(async_error) => {
+                crate::metrics::DB_RETRIES
+                    .with_label_values(&[&T::name()])
+                    .inc();
                 eprintln!(
                          "Error occurred during {}:\n{} were not stored. \n{:#?} \n Retrying in {} milliseconds...",
                          async_error,
@@ -91,7 +217,7 @@ async fn insert_retry_or_panic<T: SqlMethods + std::fmt::Debug>(
 }
 
 pub async fn select_retry_or_panic(
-    pool: &sqlx::Pool<sqlx::Postgres>,
+    db: DbHandle<'_>,
     query: &str,
     substitution_items: &[String],
 ) -> anyhow::Result<Vec<sqlx::postgres::PgRow>> {
@@ -112,17 +238,26 @@ pub async fn select_retry_or_panic(
             args.add(item);
         }
 
-        match sqlx::query_with(query, args).fetch_all(pool).await {
+        let timer = crate::metrics::DB_QUERY_DURATION_SECONDS
+            .with_label_values(&["select"])
+            .start_timer();
+        let result = db.fetch_all(query, args).await;
+        timer.observe_duration();
+
+        match result {
             Ok(res) => return Ok(res),
             Err(async_error) => {
+                crate::metrics::DB_RETRIES
+                    .with_label_values(&["select"])
+                    .inc();
                 // todo we print here select with non-filled placeholders. It would be better to get the final select statement here
                 tracing::error!(
-                         target: crate::INDEXER,
-                         "Error occurred during {}:\nFailed SELECT:\n{}\n Retrying in {} milliseconds...",
-                         async_error,
-                    query,
-                         interval.as_millis(),
-                     );
+                     target: crate::INDEXER,
+                     "Error occurred during {}:\nFailed SELECT:\n{}\n Retrying in {} milliseconds...",
+                     async_error,
+                query,
+                     interval.as_millis(),
+                 );
                 tokio::time::sleep(interval).await;
                 if interval < crate::MAX_DELAY_TIME {
                     interval *= 2;
@@ -203,10 +338,8 @@ impl PrintEnum for AccessKeyPermissionView {
 impl PrintEnum for StateChangeCauseView {
     fn print(&self) -> &str {
         match self {
-            StateChangeCauseView::NotWritableToDisk => {
-                panic!("Unexpected variant {:?} received", self)
-            }
-            StateChangeCauseView::InitialState => panic!("Unexpected variant {:?} received", self),
+            StateChangeCauseView::NotWritableToDisk => "NOT_WRITABLE_TO_DISK",
+            StateChangeCauseView::InitialState => "INITIAL_STATE",
             StateChangeCauseView::TransactionProcessing { .. } => "TRANSACTION_PROCESSING",
             StateChangeCauseView::ActionReceiptProcessingStarted { .. } => {
                 "ACTION_RECEIPT_PROCESSING_STARTED"
@@ -221,3 +354,18 @@ impl PrintEnum for StateChangeCauseView {
         }
     }
 }
+
+impl PrintEnum for StateChangeValueView {
+    fn print(&self) -> &str {
+        match self {
+            StateChangeValueView::AccountUpdate { .. } => "ACCOUNT_UPDATE",
+            StateChangeValueView::AccountDeletion { .. } => "ACCOUNT_DELETION",
+            StateChangeValueView::AccessKeyUpdate { .. } => "ACCESS_KEY_UPDATE",
+            StateChangeValueView::AccessKeyDeletion { .. } => "ACCESS_KEY_DELETION",
+            StateChangeValueView::DataUpdate { .. } => "DATA_UPDATE",
+            StateChangeValueView::DataDeletion { .. } => "DATA_DELETION",
+            StateChangeValueView::ContractCodeUpdate { .. } => "CONTRACT_CODE_UPDATE",
+            StateChangeValueView::ContractCodeDeletion { .. } => "CONTRACT_CODE_DELETION",
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use sqlx::Arguments;
+
+use crate::models::serializers::escape_nul;
+use crate::models::FieldCount;
+
+/// One row per token mentioned in the event
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct NftEvent {
+    pub receipt_id: String,
+    pub event_index: i32,
+    pub block_timestamp: BigDecimal,
+    pub contract_account_id: String,
+    pub token_id: String,
+    pub event_kind: String,
+    pub old_owner_id: Option<String>,
+    pub new_owner_id: Option<String>,
+    pub authorized_id: Option<String>,
+    pub memo: Option<String>,
+}
+
+// https://nomicon.io/Standards/Tokens/NonFungibleToken/Event
+#[derive(Deserialize, Debug)]
+struct NftMintData {
+    owner_id: String,
+    token_ids: Vec<String>,
+    memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NftBurnData {
+    owner_id: String,
+    authorized_id: Option<String>,
+    token_ids: Vec<String>,
+    memo: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct NftTransferData {
+    authorized_id: Option<String>,
+    old_owner_id: String,
+    new_owner_id: String,
+    token_ids: Vec<String>,
+    memo: Option<String>,
+}
+
+impl NftEvent {
+    /// Returns nothing for the events of other standards and for the malformed ones
+    pub fn from_event_log(
+        event_log: &crate::models::serializers::EventLog,
+        receipt_id: &near_indexer_primitives::CryptoHash,
+        contract_account_id: &near_indexer_primitives::types::AccountId,
+        block_timestamp: u64,
+    ) -> Vec<Self> {
+        if event_log.standard != "nep171" {
+            return vec![];
+        }
+        let data = match &event_log.data {
+            Some(data) => data.clone(),
+            None => return vec![],
+        };
+
+        let event = |token_id: String,
+                     event_kind: &str,
+                     old_owner_id: Option<String>,
+                     new_owner_id: Option<String>,
+                     authorized_id: Option<String>,
+                     memo: Option<String>| Self {
+            receipt_id: receipt_id.to_string(),
+            // we fill it later because we enumerate all the events of the receipt
+            event_index: 0,
+            block_timestamp: block_timestamp.into(),
+            contract_account_id: contract_account_id.to_string(),
+            token_id: escape_nul(&token_id),
+            event_kind: event_kind.to_string(),
+            old_owner_id: old_owner_id.as_deref().map(escape_nul),
+            new_owner_id: new_owner_id.as_deref().map(escape_nul),
+            authorized_id: authorized_id.as_deref().map(escape_nul),
+            memo: memo.as_deref().map(escape_nul),
+        };
+
+        match event_log.event.as_str() {
+            "nft_mint" => serde_json::from_value::<Vec<NftMintData>>(data)
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|data| {
+                    data.token_ids
+                        .into_iter()
+                        .map(|token_id| {
+                            event(
+                                token_id,
+                                "MINT",
+                                None,
+                                Some(data.owner_id.clone()),
+                                None,
+                                data.memo.clone(),
+                            )
+                        })
+                        .collect::<Vec<Self>>()
+                })
+                .collect(),
+            "nft_burn" => serde_json::from_value::<Vec<NftBurnData>>(data)
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|data| {
+                    data.token_ids
+                        .into_iter()
+                        .map(|token_id| {
+                            event(
+                                token_id,
+                                "BURN",
+                                Some(data.owner_id.clone()),
+                                None,
+                                data.authorized_id.clone(),
+                                data.memo.clone(),
+                            )
+                        })
+                        .collect::<Vec<Self>>()
+                })
+                .collect(),
+            "nft_transfer" => serde_json::from_value::<Vec<NftTransferData>>(data)
+                .unwrap_or_default()
+                .into_iter()
+                .flat_map(|data| {
+                    data.token_ids
+                        .into_iter()
+                        .map(|token_id| {
+                            event(
+                                token_id,
+                                "TRANSFER",
+                                Some(data.old_owner_id.clone()),
+                                Some(data.new_owner_id.clone()),
+                                data.authorized_id.clone(),
+                                data.memo.clone(),
+                            )
+                        })
+                        .collect::<Vec<Self>>()
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+impl crate::models::SqlMethods for NftEvent {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.receipt_id);
+        args.add(&self.event_index);
+        args.add(&self.block_timestamp);
+        args.add(&self.contract_account_id);
+        args.add(&self.token_id);
+        args.add(&self.event_kind);
+        args.add(&self.old_owner_id);
+        args.add(&self.new_owner_id);
+        args.add(&self.authorized_id);
+        args.add(&self.memo);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO nft_events VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, NftEvent::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM nft_events WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "nft_events".to_string()
+    }
+}
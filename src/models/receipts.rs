@@ -52,7 +52,7 @@ impl DataReceipt {
 }
 
 impl crate::models::SqlMethods for DataReceipt {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.receipt_id);
         args.add(&self.block_hash);
         args.add(&self.chunk_hash);
@@ -137,7 +137,7 @@ impl ActionReceipt {
 }
 
 impl models::SqlMethods for ActionReceipt {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.receipt_id);
         args.add(&self.block_hash);
         args.add(&self.chunk_hash);
@@ -208,7 +208,7 @@ impl ActionReceiptAction {
 }
 
 impl crate::models::SqlMethods for ActionReceiptAction {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.block_hash);
         args.add(&self.block_timestamp);
         args.add(&self.receipt_id);
@@ -268,7 +268,7 @@ impl ActionReceiptsOutput {
 }
 
 impl crate::models::SqlMethods for ActionReceiptsOutput {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.block_hash);
         args.add(&self.block_timestamp);
         args.add(&self.receipt_id);
@@ -131,7 +131,7 @@ pub(crate) fn extract_action_type_and_value_from_action_view(
 /// We can not store data with null-bytes in TEXT or JSONB fields
 /// of PostgreSQL
 /// ref: https://www.commandprompt.com/blog/null-characters-workarounds-arent-good-enough/
-fn escape_json(object: &mut serde_json::Value) {
+pub(crate) fn escape_json(object: &mut serde_json::Value) {
     match object {
         serde_json::Value::Object(ref mut value) => {
             for (_key, val) in value {
@@ -147,3 +147,96 @@ fn escape_json(object: &mut serde_json::Value) {
         _ => {}
     }
 }
+
+const EVENT_LOG_PREFIX: &str = "EVENT_JSON:";
+
+/// The log in the standard events format (NEP-297)
+/// https://nomicon.io/Standards/EventsFormat
+#[derive(Deserialize, Debug)]
+pub(crate) struct EventLog {
+    pub standard: String,
+    pub version: String,
+    pub event: String,
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Returns None for the plain text logs and for the events which don't follow the format.
+/// The values are kept as they are, the caller escapes them when the row is built
+pub(crate) fn parse_event_log(log: &str) -> Option<EventLog> {
+    serde_json::from_str(log.strip_prefix(EVENT_LOG_PREFIX)?.trim()).ok()
+}
+
+/// PostgreSQL text can't contain NUL character, the rest of the value is stored as it is
+pub(crate) fn escape_nul(value: &str) -> String {
+    value.replace('\0', "\\u0000")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_log_is_parsed_without_escaping() {
+        let event_log = parse_event_log(
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft_mint","data":[{"owner_id":"alice.near","token_ids":["a\"b","ü"]}]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(event_log.standard, "nep171");
+        assert_eq!(event_log.version, "1.0.0");
+        assert_eq!(event_log.event, "nft_mint");
+        assert_eq!(
+            event_log.data,
+            Some(json!([{"owner_id": "alice.near", "token_ids": ["a\"b", "ü"]}]))
+        );
+    }
+
+    #[test]
+    fn event_log_allows_spaces_and_no_data() {
+        let event_log = parse_event_log(
+            r#"EVENT_JSON: {"standard":"nep141","version":"1.0.0","event":"ft_burn"} "#,
+        )
+        .unwrap();
+
+        assert_eq!(event_log.event, "ft_burn");
+        assert_eq!(event_log.data, None);
+    }
+
+    #[test]
+    fn unknown_standard_is_still_event_log() {
+        let event_log =
+            parse_event_log(r#"EVENT_JSON:{"standard":"x","version":"0","event":"y"}"#).unwrap();
+
+        assert_eq!(event_log.standard, "x");
+    }
+
+    #[test]
+    fn plain_and_malformed_logs_are_not_event_logs() {
+        assert!(parse_event_log("Transfer 1 from alice.near to bob.near").is_none());
+        assert!(
+            parse_event_log(r#"{"standard":"nep141","version":"1.0.0","event":"ft_mint"}"#)
+                .is_none()
+        );
+        assert!(parse_event_log(r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0""#).is_none());
+        assert!(parse_event_log(r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0"}"#).is_none());
+        assert!(parse_event_log("EVENT_JSON:[]").is_none());
+    }
+
+    #[test]
+    fn nul_is_kept_by_parser_and_replaced_by_escape_nul() {
+        let event_log = parse_event_log(
+            r#"EVENT_JSON:{"standard":"nep171","version":"1.0.0","event":"nft\u0000burn"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(event_log.event, "nft\0burn");
+        assert_eq!(escape_nul(&event_log.event), "nft\\u0000burn");
+    }
+
+    #[test]
+    fn escape_nul_keeps_other_characters() {
+        assert_eq!(escape_nul("a\"b\n\\ü"), "a\"b\n\\ü");
+        assert_eq!(escape_nul("\0\0"), "\\u0000\\u0000");
+    }
+}
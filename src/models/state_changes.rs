@@ -0,0 +1,120 @@
+use bigdecimal::BigDecimal;
+use sqlx::Arguments;
+
+use crate::models::{FieldCount, PrintEnum};
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct StateChange {
+    pub block_timestamp: BigDecimal,
+    pub block_hash: String,
+    pub chunk_index_in_block: i32,
+    pub index_in_chunk: i32,
+    pub change_type: String,
+    pub account_id: String,
+    pub cause: String,
+    pub caused_by_transaction_hash: Option<String>,
+    pub caused_by_receipt_id: Option<String>,
+    pub raw: serde_json::Value,
+}
+
+impl StateChange {
+    pub fn from_state_change_with_cause(
+        state_change_with_cause: &near_indexer_primitives::views::StateChangeWithCauseView,
+        changed_in_block_hash: &near_indexer_primitives::CryptoHash,
+        changed_in_block_timestamp: u64,
+        chunk_index_in_block: i32,
+        index_in_chunk: i32,
+    ) -> Self {
+        let near_indexer_primitives::views::StateChangeWithCauseView { cause, value } =
+            state_change_with_cause;
+
+        let account_id = match value {
+            near_indexer_primitives::views::StateChangeValueView::AccountUpdate {
+                account_id,
+                ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::AccountDeletion {
+                account_id,
+            }
+            | near_indexer_primitives::views::StateChangeValueView::AccessKeyUpdate {
+                account_id,
+                ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::AccessKeyDeletion {
+                account_id,
+                ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::DataUpdate {
+                account_id, ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::DataDeletion {
+                account_id,
+                ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::ContractCodeUpdate {
+                account_id,
+                ..
+            }
+            | near_indexer_primitives::views::StateChangeValueView::ContractCodeDeletion {
+                account_id,
+            } => account_id,
+        };
+
+        let mut raw = serde_json::to_value(state_change_with_cause)
+            .expect("StateChangeWithCauseView expected to be serializable");
+        crate::models::serializers::escape_json(&mut raw);
+
+        Self {
+            block_timestamp: changed_in_block_timestamp.into(),
+            block_hash: changed_in_block_hash.to_string(),
+            chunk_index_in_block,
+            index_in_chunk,
+            change_type: value.print().to_string(),
+            account_id: account_id.to_string(),
+            cause: cause.print().to_string(),
+            caused_by_transaction_hash: match cause {
+                near_indexer_primitives::views::StateChangeCauseView::TransactionProcessing {
+                    tx_hash,
+                } => Some(tx_hash.to_string()),
+                _ => None,
+            },
+            caused_by_receipt_id: match cause {
+                near_indexer_primitives::views::StateChangeCauseView::ActionReceiptProcessingStarted { receipt_hash } => Some(receipt_hash.to_string()),
+                near_indexer_primitives::views::StateChangeCauseView::ActionReceiptGasReward { receipt_hash } => Some(receipt_hash.to_string()),
+                near_indexer_primitives::views::StateChangeCauseView::ReceiptProcessing { receipt_hash } => Some(receipt_hash.to_string()),
+                near_indexer_primitives::views::StateChangeCauseView::PostponedReceipt { receipt_hash } => Some(receipt_hash.to_string()),
+                _ => None,
+            },
+            raw,
+        }
+    }
+}
+
+impl crate::models::SqlMethods for StateChange {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.block_timestamp);
+        args.add(&self.block_hash);
+        args.add(&self.chunk_index_in_block);
+        args.add(&self.index_in_chunk);
+        args.add(&self.change_type);
+        args.add(&self.account_id);
+        args.add(&self.cause);
+        args.add(&self.caused_by_transaction_hash);
+        args.add(&self.caused_by_receipt_id);
+        args.add(&self.raw);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO state_changes VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, StateChange::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM state_changes WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "state_changes".to_string()
+    }
+}
@@ -70,7 +70,7 @@ impl Transaction {
 }
 
 impl crate::models::SqlMethods for Transaction {
-    fn add_to_args(&self, args: &mut sqlx::postgres::PgArguments) {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
         args.add(&self.transaction_hash);
         args.add(&self.block_hash);
         args.add(&self.chunk_hash);
@@ -102,3 +102,62 @@ impl crate::models::SqlMethods for Transaction {
         "transactions".to_string()
     }
 }
+
+#[derive(Debug, sqlx::FromRow, FieldCount)]
+pub struct TransactionAction {
+    pub transaction_hash: String,
+    pub index_in_transaction: i32,
+    pub block_timestamp: BigDecimal,
+    pub action_kind: String,
+    pub args: serde_json::Value,
+    pub signer_account_id: String,
+    pub receiver_account_id: String,
+}
+
+impl TransactionAction {
+    pub fn from_action_view(
+        transaction: &near_indexer_primitives::views::SignedTransactionView,
+        action_view: &near_indexer_primitives::views::ActionView,
+        index_in_transaction: i32,
+        block_timestamp: u64,
+    ) -> Self {
+        let (action_kind, args) =
+            crate::models::serializers::extract_action_type_and_value_from_action_view(action_view);
+
+        Self {
+            transaction_hash: transaction.hash.to_string(),
+            index_in_transaction,
+            block_timestamp: block_timestamp.into(),
+            action_kind,
+            args,
+            signer_account_id: transaction.signer_id.to_string(),
+            receiver_account_id: transaction.receiver_id.to_string(),
+        }
+    }
+}
+
+impl crate::models::SqlMethods for TransactionAction {
+    fn add_to_args<'q, A: Arguments<'q, Database = sqlx::Postgres>>(&'q self, args: &mut A) {
+        args.add(&self.transaction_hash);
+        args.add(&self.index_in_transaction);
+        args.add(&self.block_timestamp);
+        args.add(&self.action_kind);
+        args.add(&self.args);
+        args.add(&self.signer_account_id);
+        args.add(&self.receiver_account_id);
+    }
+
+    fn insert_query(items_count: usize) -> anyhow::Result<String> {
+        Ok("INSERT INTO transaction_actions VALUES ".to_owned()
+            + &crate::models::create_placeholders(items_count, TransactionAction::field_count())?
+            + " ON CONFLICT DO NOTHING")
+    }
+
+    fn delete_query() -> String {
+        "DELETE FROM transaction_actions WHERE block_timestamp >= $1".to_string()
+    }
+
+    fn name() -> String {
+        "transaction_actions".to_string()
+    }
+}
@@ -0,0 +1,89 @@
+use itertools::Itertools;
+
+use crate::configs::{self, Opts};
+use crate::{db_adapters, models};
+
+/// Re-indexes the blocks collected in `_blocks_to_rerun` table.
+/// The blocks are handled in ascending order with the same ReceiptsCache, so the Receipts
+/// can find their parents among the Transactions of the previously rerun blocks.
+/// The block is removed from the table only if all its Receipts are stored after the rerun
+pub(crate) async fn rerun_blocks(
+    opts: &Opts,
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    receipts_cache: crate::ReceiptsCache,
+) -> anyhow::Result<()> {
+    let db = models::DbHandle {
+        connection: models::DbConnection::Pool(pool),
+        write_mode: configs::WriteMode::Insert,
+    };
+    let block_heights = db_adapters::blocks_to_rerun::get_blocks_to_rerun(db).await?;
+    eprintln!("Found {} blocks to rerun", block_heights.len());
+
+    for block_height in block_heights {
+        let streamer_message = fetch_block(opts, block_height).await?;
+
+        let receipt_ids: Vec<String> = streamer_message
+            .shards
+            .iter()
+            .filter_map(|shard| shard.chunk.as_ref())
+            .flat_map(|chunk| {
+                chunk
+                    .receipts
+                    .iter()
+                    .map(|receipt| receipt.receipt_id.to_string())
+            })
+            .unique()
+            .collect();
+
+        // We still use non-strict mode here: the blocks we can't fix now will stay in the table
+        crate::handle_streamer_message(
+            streamer_message,
+            db,
+            receipts_cache.clone(),
+            opts,
+            false,
+            crate::BlockOrdering::sequential(),
+        )
+        .await?;
+
+        if db_adapters::blocks_to_rerun::are_all_receipts_stored(db, &receipt_ids).await? {
+            db_adapters::blocks_to_rerun::delete_block_to_rerun(db, block_height).await?;
+            eprintln!("Block {} is complete now", block_height);
+        } else {
+            eprintln!(
+                "Block {} still has Receipts without parent Transaction",
+                block_height
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Takes exactly one block from the lake
+async fn fetch_block(
+    opts: &Opts,
+    block_height: u64,
+) -> anyhow::Result<near_indexer_primitives::StreamerMessage> {
+    // The stream is dropped right after we get the block, so the lake stops fetching the next ones
+    let (reader, mut stream) = opts.streamer(block_height).await?;
+    let streamer_message = match stream.recv().await {
+        Some(streamer_message) => streamer_message,
+        None => {
+            crate::local_lake::wait_reader(reader).await?;
+            return Err(anyhow::anyhow!(
+                "The stream is closed before block {}",
+                block_height
+            ));
+        }
+    };
+
+    if streamer_message.block.header.height != block_height {
+        return Err(anyhow::anyhow!(
+            "Block {} is not found in the lake, got block {} instead",
+            block_height,
+            streamer_message.block.header.height
+        ));
+    }
+    Ok(streamer_message)
+}
@@ -0,0 +1,86 @@
+use bigdecimal::BigDecimal;
+use sqlx::{Arguments, Row};
+use tokio::sync::Mutex;
+
+use crate::configs;
+use crate::db_adapters;
+use crate::models;
+
+/// Removes all the data starting from the given block height in one transaction
+/// and moves the checkpoint back, so the indexer continues from this height
+pub(crate) async fn rollback(
+    pool: &sqlx::Pool<sqlx::Postgres>,
+    block_height: u64,
+) -> anyhow::Result<()> {
+    let transaction = Mutex::new(pool.begin().await?);
+    let db = models::DbHandle {
+        connection: models::DbConnection::Transaction(&transaction),
+        write_mode: configs::WriteMode::Insert,
+    };
+
+    // The block with the given height could be skipped, so we take the first existing one after it
+    let query = "SELECT block_timestamp FROM blocks WHERE block_height >= $1::numeric
+                 ORDER BY block_height LIMIT 1";
+    let res = models::select_retry_or_panic(db, query, &[block_height.to_string()]).await?;
+
+    if let Some(row) = res.first() {
+        let block_timestamp: BigDecimal = row.get(0);
+        eprintln!(
+            "Removing the data starting from block timestamp {}",
+            block_timestamp
+        );
+        // The order is reversed to the order of FKs
+        db_adapters::access_keys::restore_access_keys_deleted_from(db, &block_timestamp).await?;
+        delete_from::<models::AccessKey>(db, &block_timestamp).await?;
+        delete_from::<models::StateChange>(db, &block_timestamp).await?;
+        delete_from::<models::AccountChange>(db, &block_timestamp).await?;
+        db_adapters::accounts::restore_accounts_deleted_from(db, &block_timestamp).await?;
+        delete_from::<models::Account>(db, &block_timestamp).await?;
+        delete_from::<models::ContractDeployment>(db, &block_timestamp).await?;
+        delete_from::<models::ContractStateChange>(db, &block_timestamp).await?;
+        delete_from::<models::ValidatorShard>(db, &block_timestamp).await?;
+        delete_from::<models::Validator>(db, &block_timestamp).await?;
+        delete_from::<models::Epoch>(db, &block_timestamp).await?;
+        // the remaining epochs are recounted by the blocks and the chunks, so they go before them
+        db_adapters::epochs::restore_epochs_ended_from(db, &block_timestamp).await?;
+        delete_from::<models::NftEvent>(db, &block_timestamp).await?;
+        delete_from::<models::FtEvent>(db, &block_timestamp).await?;
+        delete_from::<models::ExecutionOutcomeProof>(db, &block_timestamp).await?;
+        delete_from::<models::ExecutionOutcomeGasProfile>(db, &block_timestamp).await?;
+        delete_from::<models::ExecutionOutcomeLog>(db, &block_timestamp).await?;
+        delete_from::<models::ExecutionOutcomeReceipt>(db, &block_timestamp).await?;
+        delete_from::<models::ExecutionOutcome>(db, &block_timestamp).await?;
+        delete_from::<models::ActionReceiptAction>(db, &block_timestamp).await?;
+        delete_from::<models::ActionReceiptsOutput>(db, &block_timestamp).await?;
+        delete_from::<models::DataReceipt>(db, &block_timestamp).await?;
+        delete_from::<models::ActionReceipt>(db, &block_timestamp).await?;
+        delete_from::<models::TransactionAction>(db, &block_timestamp).await?;
+        delete_from::<models::Transaction>(db, &block_timestamp).await?;
+        delete_from::<models::Chunk>(db, &block_timestamp).await?;
+        delete_from::<models::Block>(db, &block_timestamp).await?;
+    } else {
+        eprintln!("There are no blocks starting from {}", block_height);
+    }
+
+    db_adapters::blocks_to_rerun::delete_blocks_to_rerun_from(db, block_height).await?;
+    db_adapters::checkpoints::rollback_last_processed_block_height(
+        db,
+        block_height.saturating_sub(1),
+    )
+    .await?;
+
+    transaction.into_inner().commit().await?;
+    eprintln!("Rollback to block {} is finished", block_height);
+    Ok(())
+}
+
+async fn delete_from<T: models::SqlMethods>(
+    db: models::DbHandle<'_>,
+    block_timestamp: &BigDecimal,
+) -> anyhow::Result<()> {
+    let mut args = sqlx::postgres::PgArguments::default();
+    args.add(block_timestamp.clone());
+    db.execute(&T::delete_query(), args).await?;
+    eprintln!("{} are cleaned up", T::name());
+    Ok(())
+}